@@ -0,0 +1,272 @@
+use std::borrow::Cow;
+
+pub fn escape_text(text: &str) -> Cow<'_, str> {
+    escape(text, |c| match c {
+        '&' => Some("&amp;"),
+        '<' => Some("&lt;"),
+        '>' => Some("&gt;"),
+        _ => None,
+    })
+}
+
+pub fn escape_attribute(value: &str) -> Cow<'_, str> {
+    escape(value, |c| match c {
+        '&' => Some("&amp;"),
+        '<' => Some("&lt;"),
+        '>' => Some("&gt;"),
+        '"' => Some("&quot;"),
+        '\'' => Some("&#39;"),
+        _ => None,
+    })
+}
+
+pub fn escape_url(url: &str) -> Cow<'_, str> {
+    let encode = |c: char| {
+        !c.is_ascii()
+            || c.is_ascii_control()
+            || c.is_ascii_whitespace()
+            || c == '"'
+            || c == '<'
+            || c == '>'
+            || c == '`'
+    };
+
+    if !url.trim().contains(encode) {
+        return Cow::Borrowed(url.trim());
+    }
+
+    let mut output = String::with_capacity(url.len() + 8);
+    let mut buffer = [0; 4];
+
+    for c in url.trim().chars() {
+        if encode(c) {
+            for byte in c.encode_utf8(&mut buffer).bytes() {
+                output.push_str(&format!("%{:02X}", byte));
+            }
+        } else {
+            output.push(c);
+        }
+    }
+
+    Cow::Owned(output)
+}
+
+pub fn escape_raw_text(text: &str) -> Cow<'_, str> {
+    if !text.contains("</") {
+        return Cow::Borrowed(text);
+    }
+
+    Cow::Owned(text.replace("</", "<\\/"))
+}
+
+pub fn escape_instruction(data: &str) -> Cow<'_, str> {
+    if !data.contains("?>") {
+        return Cow::Borrowed(data);
+    }
+
+    Cow::Owned(data.replace("?>", "? >"))
+}
+
+pub fn escape_bogus_comment(data: &str) -> Cow<'_, str> {
+    escape(data, |c| match c {
+        '>' => Some("&gt;"),
+        _ => None,
+    })
+}
+
+pub fn escape_comment(comment: &str) -> Cow<'_, str> {
+    if !comment.contains("--")
+        && !comment.starts_with('>')
+        && !comment.starts_with('-')
+        && !comment.ends_with('-')
+    {
+        return Cow::Borrowed(comment);
+    }
+
+    let mut output = String::with_capacity(comment.len() + 2);
+
+    if comment.starts_with('>') || comment.starts_with('-') {
+        output.push(' ');
+    }
+
+    for c in comment.chars() {
+        if c == '-' && output.ends_with('-') {
+            output.push(' ');
+        }
+
+        output.push(c);
+    }
+
+    if output.ends_with('-') {
+        output.push(' ');
+    }
+
+    Cow::Owned(output)
+}
+
+pub fn is_void(tag: &str) -> bool {
+    match tag {
+        "area" | "base" | "br" | "col" | "command" | "embed" | "hr" | "img" | "input"
+        | "keygen" | "link" | "meta" | "param" | "source" | "track" | "wbr" => true,
+        _ => false,
+    }
+}
+
+pub fn is_valid_tag(tag: &str) -> bool {
+    let mut chars = tag.chars();
+
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() => {
+            chars.all(|c| c.is_alphanumeric() || c == '-' || c == '_' || c == '.' || c == ':')
+        }
+        _ => false,
+    }
+}
+
+pub fn is_valid_attribute(key: &str) -> bool {
+    !key.is_empty()
+        && key.chars().all(|c| {
+            !c.is_control()
+                && !c.is_whitespace()
+                && c != '"'
+                && c != '\''
+                && c != '<'
+                && c != '>'
+                && c != '/'
+                && c != '='
+        })
+}
+
+fn escape<F>(input: &str, replace: F) -> Cow<'_, str>
+where
+    F: Fn(char) -> Option<&'static str>,
+{
+    let mut output = String::new();
+    let mut last = 0;
+
+    for (index, c) in input.char_indices() {
+        if let Some(replacement) = replace(c) {
+            output.push_str(&input[last..index]);
+            output.push_str(replacement);
+            last = index + c.len_utf8();
+        }
+    }
+
+    if last == 0 {
+        return Cow::Borrowed(input);
+    }
+
+    output.push_str(&input[last..]);
+
+    Cow::Owned(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::{
+        escape_attribute, escape_bogus_comment, escape_comment, escape_raw_text, escape_text,
+        escape_url, is_valid_attribute, is_valid_tag, is_void,
+    };
+
+    #[test]
+    fn test_escape_text() {
+        assert_eq!(escape_text("hello world"), Cow::Borrowed("hello world"));
+        assert_eq!(
+            escape_text("<script>alert('x & y')</script>"),
+            "&lt;script&gt;alert('x &amp; y')&lt;/script&gt;"
+        );
+        assert_eq!(escape_text("\"quoted\""), "\"quoted\"");
+        assert_eq!(escape_text("caf\u{e9} & bar"), "caf\u{e9} &amp; bar");
+    }
+
+    #[test]
+    fn test_escape_attribute() {
+        assert_eq!(
+            escape_attribute("hello world"),
+            Cow::Borrowed("hello world")
+        );
+        assert_eq!(
+            escape_attribute("\" onclick=\"alert('x')"),
+            "&quot; onclick=&quot;alert(&#39;x&#39;)"
+        );
+        assert_eq!(
+            escape_attribute("a < b && c > d"),
+            "a &lt; b &amp;&amp; c &gt; d"
+        );
+    }
+
+    #[test]
+    fn test_escape_url() {
+        assert_eq!(
+            escape_url("/search?q=a&b=c"),
+            Cow::Borrowed("/search?q=a&b=c")
+        );
+        assert_eq!(
+            escape_url(" /a b/\"quoted\"/caf\u{e9} "),
+            "/a%20b/%22quoted%22/caf%C3%A9"
+        );
+    }
+
+    #[test]
+    fn test_escape_raw_text() {
+        assert_eq!(
+            escape_raw_text("if (a < b && c > d) {}"),
+            Cow::Borrowed("if (a < b && c > d) {}")
+        );
+        assert_eq!(
+            escape_raw_text("document.write('</script><script>alert(1)</script>')"),
+            "document.write('<\\/script><script>alert(1)<\\/script>')"
+        );
+    }
+
+    #[test]
+    fn test_escape_bogus_comment() {
+        assert_eq!(escape_bogus_comment("a b"), Cow::Borrowed("a b"));
+        assert_eq!(escape_bogus_comment("x><script>"), "x&gt;<script&gt;");
+    }
+
+    #[test]
+    fn test_escape_comment() {
+        assert_eq!(escape_comment("hello world"), Cow::Borrowed("hello world"));
+        assert_eq!(escape_comment("a--b"), "a- -b");
+        assert_eq!(escape_comment("a---b"), "a- - -b");
+        assert_eq!(escape_comment("-->"), " - ->");
+        assert_eq!(escape_comment(">"), " >");
+        assert_eq!(escape_comment("<!--"), "<!- - ");
+        assert_eq!(escape_comment("hello-"), "hello- ");
+    }
+
+    #[test]
+    fn test_void() {
+        assert!(is_void("br"));
+        assert!(is_void("input"));
+        assert!(!is_void("p"));
+        assert!(!is_void("BR"));
+    }
+
+    #[test]
+    fn test_valid_tag() {
+        assert!(is_valid_tag("div"));
+        assert!(is_valid_tag("h1"));
+        assert!(is_valid_tag("custom-element"));
+        assert!(!is_valid_tag(""));
+        assert!(!is_valid_tag("1div"));
+        assert!(!is_valid_tag("div onclick"));
+        assert!(!is_valid_tag("div>"));
+        assert!(!is_valid_tag("script/"));
+    }
+
+    #[test]
+    fn test_valid_attribute() {
+        assert!(is_valid_attribute("class"));
+        assert!(is_valid_attribute("data-user-id"));
+        assert!(is_valid_attribute("@click"));
+        assert!(!is_valid_attribute(""));
+        assert!(!is_valid_attribute("on click"));
+        assert!(!is_valid_attribute("a=b"));
+        assert!(!is_valid_attribute("\"a"));
+        assert!(!is_valid_attribute("a>"));
+    }
+}
@@ -0,0 +1,284 @@
+use proc_macro2::Span;
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream, Result};
+use syn::punctuated::Punctuated;
+use syn::{braced, bracketed, parenthesized, Expr, Ident, Lit, LitBool, LitStr, Token};
+
+pub struct Markup {
+    pub nodes: Vec<Node>,
+}
+
+impl Parse for Markup {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            nodes: parse_nodes(input)?,
+        })
+    }
+}
+
+pub enum Node {
+    Text(String),
+    Block(String),
+    Raw(String),
+    Comment(String),
+    Element(Element),
+    Expr(Box<Expr>),
+}
+
+impl Parse for Node {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(LitStr) {
+            let lit = input.parse::<LitStr>()?;
+
+            if lit.token().to_string().starts_with('r') {
+                return Ok(Node::Block(dedent(&lit.value())));
+            }
+
+            return Ok(Node::Text(collapse(&lit.value())));
+        }
+
+        if input.peek(Token![!]) {
+            input.parse::<Token![!]>()?;
+
+            return Ok(Node::Raw(input.parse::<LitStr>()?.value()));
+        }
+
+        if input.peek(Token![#]) {
+            return parse_comment(input);
+        }
+
+        if input.peek(syn::token::Paren) {
+            let content;
+
+            parenthesized!(content in input);
+
+            return Ok(Node::Expr(Box::new(content.parse()?)));
+        }
+
+        if input.peek(Ident::peek_any) {
+            return Ok(Node::Element(input.parse()?));
+        }
+
+        Err(input.error("expected a text, raw, comment, expression or element node"))
+    }
+}
+
+pub struct Element {
+    pub tag: Name,
+    pub attrs: Vec<Attr>,
+    pub nodes: Vec<Node>,
+}
+
+impl Parse for Element {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let tag = input.parse::<Name>()?;
+        let mut attrs = Vec::new();
+
+        if is_attr(input) {
+            loop {
+                attrs.push(input.parse::<Attr>()?);
+
+                if !input.peek(Token![,]) {
+                    break;
+                }
+
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        let nodes = if input.peek(syn::token::Brace) {
+            let content;
+
+            braced!(content in input);
+            parse_nodes(&content)?
+        } else if input.peek(Token![|]) {
+            input.parse::<Token![|]>()?;
+
+            vec![input.parse::<Node>()?]
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self { tag, attrs, nodes })
+    }
+}
+
+pub struct Attr {
+    pub name: Name,
+    pub value: Value,
+}
+
+impl Parse for Attr {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name = input.parse::<Name>()?;
+        let value = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            input.parse::<Value>()?
+        } else {
+            Value::Boolean(true)
+        };
+
+        Ok(Self { name, value })
+    }
+}
+
+pub enum Value {
+    String(String),
+    Boolean(bool),
+    Number(f64),
+    Tokens(Vec<String>),
+    Url(String),
+    Expr(Box<Expr>),
+}
+
+impl Parse for Value {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(LitStr) {
+            return Ok(Value::String(input.parse::<LitStr>()?.value()));
+        }
+
+        if input.peek(LitBool) {
+            return Ok(Value::Boolean(input.parse::<LitBool>()?.value));
+        }
+
+        if input.peek(Token![-]) || input.peek(Lit) {
+            let negative = input.parse::<Option<Token![-]>>()?.is_some();
+            let number = match input.parse::<Lit>()? {
+                Lit::Int(lit) => lit.base10_parse::<f64>()?,
+                Lit::Float(lit) => lit.base10_parse::<f64>()?,
+                lit => return Err(syn::Error::new(lit.span(), "expected a number")),
+            };
+
+            return Ok(Value::Number(if negative { -number } else { number }));
+        }
+
+        if input.peek(syn::token::Bracket) {
+            let content;
+
+            bracketed!(content in input);
+
+            let tokens = Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?;
+
+            return Ok(Value::Tokens(tokens.iter().map(LitStr::value).collect()));
+        }
+
+        if input.peek(syn::token::Paren) {
+            let content;
+
+            parenthesized!(content in input);
+
+            return Ok(Value::Expr(Box::new(content.parse()?)));
+        }
+
+        if input.peek(Ident) && input.peek2(syn::token::Paren) {
+            let ident = input.parse::<Ident>()?;
+
+            if ident != "url" {
+                return Err(syn::Error::new(ident.span(), "expected `url`"));
+            }
+
+            let content;
+
+            parenthesized!(content in input);
+
+            return Ok(Value::Url(content.parse::<LitStr>()?.value()));
+        }
+
+        Err(input.error("expected an attribute value"))
+    }
+}
+
+pub struct Name {
+    pub value: String,
+    pub span: Span,
+}
+
+impl Parse for Name {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ident = input.call(Ident::parse_any)?;
+        let span = ident.span();
+        let mut value = ident.unraw().to_string();
+
+        if !value.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            return Err(syn::Error::new(
+                span,
+                "expected a name starting with a letter",
+            ));
+        }
+
+        while input.peek(Token![-]) && input.peek2(Ident::peek_any) {
+            input.parse::<Token![-]>()?;
+            value.push('-');
+            value.push_str(&input.call(Ident::parse_any)?.unraw().to_string());
+        }
+
+        Ok(Self { value, span })
+    }
+}
+
+fn parse_nodes(input: ParseStream) -> Result<Vec<Node>> {
+    let mut nodes = Vec::new();
+
+    while !input.is_empty() {
+        nodes.push(input.parse::<Node>()?);
+    }
+
+    Ok(nodes)
+}
+
+fn parse_comment(input: ParseStream) -> Result<Node> {
+    let content;
+
+    input.parse::<Token![#]>()?;
+    bracketed!(content in input);
+
+    let ident = content.parse::<Ident>()?;
+
+    if ident != "doc" {
+        return Err(syn::Error::new(ident.span(), "expected a `///` comment"));
+    }
+
+    content.parse::<Token![=]>()?;
+
+    Ok(Node::Comment(
+        content.parse::<LitStr>()?.value().trim().to_owned(),
+    ))
+}
+
+fn is_attr(input: ParseStream) -> bool {
+    let fork = input.fork();
+
+    fork.parse::<Name>().is_ok() && (fork.peek(Token![=]) || fork.peek(Token![,]))
+}
+
+fn collapse(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn dedent(block: &str) -> String {
+    let block = if block.starts_with("\r\n") {
+        &block[2..]
+    } else if block.starts_with('\n') {
+        &block[1..]
+    } else {
+        return block.to_owned();
+    };
+    let mut lines = block.lines().collect::<Vec<_>>();
+
+    if lines.len() > 1 && lines.last().map_or(false, |line| line.trim().is_empty()) {
+        lines.pop();
+    }
+
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    lines
+        .iter()
+        .map(|line| line.get(indent..).unwrap_or_else(|| line.trim_start()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,93 @@
+use proc_macro2::{Ident, Literal, Span, TokenStream};
+use quote::{quote, quote_spanned};
+
+use crate::ast::{Attr, Element, Markup, Node, Value};
+
+pub fn expand(markup: &Markup) -> TokenStream {
+    expand_nodes(&markup.nodes)
+}
+
+fn expand_nodes(nodes: &[Node]) -> TokenStream {
+    let ident = nodes_ident();
+    let nodes = nodes.iter().map(expand_node);
+
+    quote! {
+        {
+            let mut #ident = ::brace_web_markup::Nodes::new();
+            #( #nodes; )*
+            #ident
+        }
+    }
+}
+
+fn expand_node(node: &Node) -> TokenStream {
+    let nodes = nodes_ident();
+
+    match node {
+        Node::Text(text) | Node::Block(text) => quote! {
+            #nodes.append(::brace_web_markup::Text::preserved(#text))
+        },
+        Node::Raw(raw) => quote! {
+            #nodes.append(::brace_web_markup::Raw::from(#raw))
+        },
+        Node::Comment(comment) => quote! {
+            #nodes.append(::brace_web_markup::Comment::from(#comment))
+        },
+        Node::Element(element) => {
+            let element = expand_element(element);
+
+            quote! {
+                #nodes.append(#element)
+            }
+        }
+        Node::Expr(expr) => quote! {
+            #nodes.extend(::brace_web_markup::Nodes::from(#expr))
+        },
+    }
+}
+
+fn expand_element(element: &Element) -> TokenStream {
+    let tag = &element.tag.value;
+    let attrs = element.attrs.iter().map(expand_attr);
+    let nodes = if element.nodes.is_empty() {
+        TokenStream::new()
+    } else {
+        let nodes = expand_nodes(&element.nodes);
+
+        quote!(.with_nodes(#nodes))
+    };
+
+    quote_spanned! {element.tag.span=>
+        ::brace_web_markup::Element::new(#tag)
+            #( #attrs )*
+            #nodes
+    }
+}
+
+fn expand_attr(attr: &Attr) -> TokenStream {
+    let name = &attr.name.value;
+    let value = match &attr.value {
+        Value::String(string) => quote!(#string),
+        Value::Boolean(boolean) => quote!(#boolean),
+        Value::Number(number) => {
+            let number = Literal::f64_unsuffixed(*number);
+
+            quote!(::brace_web_markup::Attribute::Number(#number))
+        }
+        Value::Tokens(tokens) => quote! {
+            ::brace_web_markup::Attribute::Tokens(
+                ::std::vec![#( ::std::string::String::from(#tokens) ),*]
+            )
+        },
+        Value::Url(url) => quote!(::brace_web_markup::Attribute::url(#url)),
+        Value::Expr(expr) => quote!(#expr),
+    };
+
+    quote_spanned! {attr.name.span=>
+        .with_attr(#name, #value)
+    }
+}
+
+fn nodes_ident() -> Ident {
+    Ident::new("nodes", Span::mixed_site())
+}
@@ -0,0 +1,28 @@
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::parse_macro_input;
+
+use crate::ast::Markup;
+
+mod ast;
+mod expand;
+mod render;
+
+#[proc_macro]
+pub fn markup(input: TokenStream) -> TokenStream {
+    let markup = parse_macro_input!(input as Markup);
+
+    expand::expand(&markup).into()
+}
+
+#[proc_macro]
+pub fn markup_str(input: TokenStream) -> TokenStream {
+    let markup = parse_macro_input!(input as Markup);
+
+    match render::render(&markup) {
+        Ok(output) => quote!(#output).into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
@@ -0,0 +1,95 @@
+use std::fmt::Write;
+
+use brace_web_markup_escape::{
+    escape_attribute, escape_comment, escape_raw_text, escape_text, escape_url, is_void,
+};
+use syn::{Error, Result};
+
+use crate::ast::{Element, Markup, Node, Value};
+
+pub fn render(markup: &Markup) -> Result<String> {
+    let mut output = String::new();
+
+    render_nodes(&markup.nodes, false, &mut output)?;
+
+    Ok(output)
+}
+
+fn render_nodes(nodes: &[Node], raw_text: bool, output: &mut String) -> Result<()> {
+    for node in nodes {
+        match node {
+            Node::Text(text) | Node::Block(text) if raw_text => {
+                output.push_str(&escape_raw_text(text))
+            }
+            Node::Text(text) | Node::Block(text) => output.push_str(&escape_text(text)),
+            Node::Raw(raw) => output.push_str(raw),
+            Node::Comment(comment) => {
+                output.push_str("<!--");
+                output.push_str(&escape_comment(comment));
+                output.push_str("-->");
+            }
+            Node::Element(element) => render_element(element, output)?,
+            Node::Expr(expr) => {
+                return Err(Error::new_spanned(
+                    expr,
+                    "expressions are not supported in static markup",
+                ))
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn render_element(element: &Element, output: &mut String) -> Result<()> {
+    let tag = element.tag.value.as_str();
+
+    output.push('<');
+    output.push_str(tag);
+
+    for attr in &element.attrs {
+        let name = attr.name.value.as_str();
+        let value = match &attr.value {
+            Value::String(string) => string.clone(),
+            Value::Boolean(true) => {
+                let _ = write!(output, " {}", name);
+
+                continue;
+            }
+            Value::Boolean(false) => continue,
+            Value::Number(number) => number.to_string(),
+            Value::Tokens(tokens) => tokens.join(" "),
+            Value::Url(url) => escape_url(url).into_owned(),
+            Value::Expr(expr) => {
+                return Err(Error::new_spanned(
+                    expr,
+                    "expressions are not supported in static markup",
+                ))
+            }
+        };
+
+        let _ = write!(output, " {}=\"", name);
+        output.push_str(&escape_attribute(&value));
+        output.push('"');
+    }
+
+    if is_void(tag) {
+        output.push_str(" />");
+
+        return Ok(());
+    }
+
+    output.push('>');
+    render_nodes(&element.nodes, is_raw_text(tag), output)?;
+
+    let _ = write!(output, "</{}>", tag);
+
+    Ok(())
+}
+
+fn is_raw_text(tag: &str) -> bool {
+    match tag {
+        "script" | "style" => true,
+        _ => false,
+    }
+}
@@ -1,9 +1,18 @@
+pub use brace_web_markup_macros::{markup, markup_str};
+
+pub use crate::tree::comment::{comment, Comment};
 pub use crate::tree::document::{document, Document};
 pub use crate::tree::element::attribute::{Attribute, Attributes};
 pub use crate::tree::element::tag::*;
+pub use crate::tree::element::value::*;
 pub use crate::tree::element::{element, Element};
+pub use crate::tree::instruction::{instruction, Instruction};
 pub use crate::tree::node::{Node, Nodes};
+pub use crate::tree::raw::{raw, Raw};
 pub use crate::tree::text::{text, Text};
+pub use crate::util::negotiate::Negotiated;
+pub use crate::util::partial::Partial;
+pub use crate::util::response::Html;
 
 pub mod tree;
 pub mod util;
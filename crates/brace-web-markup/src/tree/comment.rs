@@ -0,0 +1,93 @@
+use std::fmt::{self, Display, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::util::escape::escape_comment;
+use crate::util::render::{Render, Renderer, Result as RenderResult};
+
+pub fn comment<T>(comment: T) -> Comment
+where
+    T: Into<String>,
+{
+    Comment::new(comment)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Comment {
+    comment: String,
+}
+
+impl Comment {
+    pub fn new<T>(comment: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self {
+            comment: comment.into(),
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.comment
+    }
+
+    pub fn value_mut(&mut self) -> &mut String {
+        &mut self.comment
+    }
+}
+
+impl Render for Comment {
+    fn render(&self, renderer: &mut Renderer) -> RenderResult {
+        Ok(write!(
+            renderer,
+            "<!--{}-->",
+            escape_comment(&self.comment)
+        )?)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.comment.len() + 7
+    }
+}
+
+impl Display for Comment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&self.comment, f)
+    }
+}
+
+impl From<&str> for Comment {
+    fn from(from: &str) -> Self {
+        Self::new(from)
+    }
+}
+
+impl From<String> for Comment {
+    fn from(from: String) -> Self {
+        Self::new(from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Comment;
+    use crate::util::render::render;
+
+    #[test]
+    fn test_comment_render() {
+        assert_eq!(render(&Comment::new("hello")).unwrap(), "<!--hello-->");
+        assert_eq!(
+            render(&Comment::new(" hello world ")).unwrap(),
+            "<!-- hello world -->"
+        );
+        assert_eq!(
+            render(&Comment::new("a -- b --> c")).unwrap(),
+            "<!--a - - b - -> c-->"
+        );
+        assert_eq!(
+            render(&Comment::new("--><script>")).unwrap(),
+            "<!-- - -><script>-->"
+        );
+    }
+}
@@ -1,12 +1,20 @@
 use std::fmt::Write;
+use std::mem;
 
 use futures::future::{self, Ready};
 use serde::{Deserialize, Serialize};
 
 use brace_web_core::{HttpRequest, HttpResponse, Responder};
 
-use crate::util::render::{render, Error, Render, Renderer, Result as RenderResult};
-use crate::{Node, Nodes};
+use crate::tree::visit::{
+    Ancestors, BreadthFirst, Descendants, Elements, Paths, Texts, Visit, VisitMut, Visitor,
+    VisitorMut,
+};
+use crate::util::escape::escape_attribute;
+use crate::util::render::{Error, Frame, Render, Renderer, RendererConfig, Result as RenderResult};
+use crate::util::selector::Error as SelectorError;
+use crate::util::stream::respond;
+use crate::{Attribute, Element, Node, Nodes, Text};
 
 pub fn document() -> Document {
     Document::new()
@@ -14,16 +22,33 @@ pub fn document() -> Document {
 
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct Document {
+    #[serde(default, skip_serializing_if = "Doctype::is_html")]
+    doctype: Doctype,
     nodes: Nodes,
 }
 
 impl Document {
     pub fn new() -> Self {
         Self {
+            doctype: Doctype::Html,
             nodes: Nodes::new(),
         }
     }
 
+    pub fn doctype(&self) -> &Doctype {
+        &self.doctype
+    }
+
+    pub fn set_doctype(&mut self, doctype: Doctype) -> &mut Self {
+        self.doctype = doctype;
+        self
+    }
+
+    pub fn with_doctype(mut self, doctype: Doctype) -> Self {
+        self.doctype = doctype;
+        self
+    }
+
     pub fn nodes(&self) -> &Nodes {
         &self.nodes
     }
@@ -55,17 +80,266 @@ impl Document {
         self.nodes.extend(nodes);
         self
     }
+
+    pub fn get_path(&self, path: &[usize]) -> Option<&Node> {
+        self.nodes.get_path(path)
+    }
+
+    pub fn get_path_mut(&mut self, path: &[usize]) -> Option<&mut Node> {
+        self.nodes.get_path_mut(path)
+    }
+
+    pub fn descendants(&self) -> Descendants<'_> {
+        self.nodes.descendants()
+    }
+
+    pub fn breadth_first(&self) -> BreadthFirst<'_> {
+        self.nodes.breadth_first()
+    }
+
+    pub fn paths(&self) -> Paths<'_> {
+        self.nodes.paths()
+    }
+
+    pub fn ancestors(&self, path: &[usize]) -> Ancestors<'_> {
+        self.nodes.ancestors(path)
+    }
+
+    pub fn elements(&self) -> Elements<'_> {
+        self.nodes.elements()
+    }
+
+    pub fn texts(&self) -> Texts<'_> {
+        self.nodes.texts()
+    }
+
+    pub fn visit<V>(&self, visitor: &mut V) -> Visit
+    where
+        V: Visitor,
+    {
+        self.nodes.visit(visitor)
+    }
+
+    pub fn visit_mut<V>(&mut self, visitor: &mut V) -> VisitMut
+    where
+        V: VisitorMut,
+    {
+        self.nodes.visit_mut(visitor)
+    }
+
+    pub fn select(&self, selector: &str) -> Result<Vec<&Element>, SelectorError> {
+        self.nodes.select(selector)
+    }
+
+    pub fn select_first(&self, selector: &str) -> Result<Option<&Element>, SelectorError> {
+        self.nodes.select_first(selector)
+    }
+
+    pub fn select_first_mut(
+        &mut self,
+        selector: &str,
+    ) -> Result<Option<&mut Element>, SelectorError> {
+        self.nodes.select_first_mut(selector)
+    }
+
+    pub fn select_mut<F>(&mut self, selector: &str, f: F) -> Result<usize, SelectorError>
+    where
+        F: FnMut(&mut Element),
+    {
+        self.nodes.select_mut(selector, f)
+    }
+
+    pub fn html(&self) -> Option<&Element> {
+        child(&self.nodes, "html")
+    }
+
+    pub fn html_mut(&mut self) -> &mut Element {
+        if child(&self.nodes, "html").is_none() {
+            let nodes = mem::take(&mut self.nodes);
+
+            self.nodes.append(Element::new("html").with_nodes(nodes));
+        }
+
+        child_or_insert(&mut self.nodes, "html", false)
+    }
+
+    pub fn head(&self) -> Option<&Element> {
+        child(self.html()?.nodes(), "head")
+    }
+
+    pub fn head_mut(&mut self) -> &mut Element {
+        child_or_insert(self.html_mut().nodes_mut(), "head", true)
+    }
+
+    pub fn body(&self) -> Option<&Element> {
+        child(self.html()?.nodes(), "body")
+    }
+
+    pub fn body_mut(&mut self) -> &mut Element {
+        let html = self.html_mut();
+
+        if child(html.nodes(), "body").is_none() {
+            let (head, body): (Vec<Node>, Vec<Node>) = mem::take(html.nodes_mut())
+                .into_iter()
+                .partition(|node| is_tag(node, "head"));
+
+            html.nodes_mut().extend(head);
+            html.nodes_mut()
+                .append(Element::new("body").with_nodes(body));
+        }
+
+        child_or_insert(html.nodes_mut(), "body", false)
+    }
+
+    pub fn lang(&self) -> Option<&str> {
+        self.html()?
+            .get_attr("lang")
+            .and_then(Attribute::as_string)
+            .map(String::as_str)
+    }
+
+    pub fn set_lang<T>(&mut self, lang: T) -> &mut Self
+    where
+        T: Into<String>,
+    {
+        self.html_mut().set_attr("lang", lang.into());
+        self
+    }
+
+    pub fn with_lang<T>(mut self, lang: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.set_lang(lang);
+        self
+    }
+
+    pub fn title(&self) -> Option<&str> {
+        child(self.head()?.nodes(), "title")?
+            .nodes()
+            .iter()
+            .find_map(Node::as_text)
+            .map(Text::value)
+    }
+
+    pub fn set_title<T>(&mut self, title: T) -> &mut Self
+    where
+        T: Into<String>,
+    {
+        let element = child_or_insert(self.head_mut().nodes_mut(), "title", false);
+
+        *element.nodes_mut() = Nodes::from(Text::new(title.into()));
+        self
+    }
+
+    pub fn with_title<T>(mut self, title: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.set_title(title);
+        self
+    }
+
+    pub fn meta(&self, name: &str) -> Option<&str> {
+        self.head()?
+            .nodes()
+            .iter()
+            .filter_map(Node::as_element)
+            .find(|element| is_meta(element, name))?
+            .get_attr("content")
+            .and_then(Attribute::as_string)
+            .map(String::as_str)
+    }
+
+    pub fn set_meta<N, C>(&mut self, name: N, content: C) -> &mut Self
+    where
+        N: Into<String>,
+        C: Into<String>,
+    {
+        let name = name.into();
+        let nodes = self.head_mut().nodes_mut();
+        let index = nodes.iter().position(|node| {
+            node.as_element()
+                .map_or(false, |element| is_meta(element, &name))
+        });
+
+        match index.and_then(|index| nodes.get_mut(index)) {
+            Some(Node::Element(meta)) => {
+                meta.set_attr("content", content.into());
+            }
+            _ => {
+                nodes.append(
+                    Element::new("meta")
+                        .with_attr("name", name)
+                        .with_attr("content", content.into()),
+                );
+            }
+        }
+
+        self
+    }
+
+    pub fn with_meta<N, C>(mut self, name: N, content: C) -> Self
+    where
+        N: Into<String>,
+        C: Into<String>,
+    {
+        self.set_meta(name, content);
+        self
+    }
+
+    pub(crate) fn has_prolog(&self, config: &RendererConfig) -> bool {
+        match self.doctype {
+            Doctype::Html => !config.is_xml() || config.has_declaration(),
+            Doctype::None => config.has_declaration(),
+            _ => true,
+        }
+    }
+
+    pub(crate) fn render_doctype(&self, renderer: &mut Renderer) -> RenderResult {
+        let declaration = renderer.config().has_declaration() || self.doctype == Doctype::Xml;
+
+        if declaration {
+            write!(renderer, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+        }
+
+        match &self.doctype {
+            Doctype::Html if renderer.is_xml() => Ok(()),
+            Doctype::Html => {
+                if declaration {
+                    renderer.newline()?;
+                }
+
+                Ok(write!(renderer, "<!DOCTYPE html>")?)
+            }
+            Doctype::Legacy { public, system } => {
+                if declaration {
+                    renderer.newline()?;
+                }
+
+                Ok(write!(
+                    renderer,
+                    "<!DOCTYPE html PUBLIC \"{}\" \"{}\">",
+                    escape_attribute(public),
+                    escape_attribute(system)
+                )?)
+            }
+            Doctype::Xml | Doctype::None => Ok(()),
+        }
+    }
 }
 
 impl Render for Document {
     fn render(&self, renderer: &mut Renderer) -> RenderResult {
-        write!(renderer, "<!DOCTYPE html>")?;
+        self.render_doctype(renderer)?;
 
-        for node in &self.nodes {
-            node.render(renderer)?;
-        }
+        let frame = Frame::root(renderer, self.has_prolog(renderer.config()));
 
-        Ok(())
+        renderer.walk(&self.nodes, None, frame)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.doctype.size_hint() + self.nodes.size_hint()
     }
 }
 
@@ -73,15 +347,8 @@ impl Responder for Document {
     type Error = Error;
     type Future = Ready<Result<HttpResponse, Self::Error>>;
 
-    fn respond_to(self, _: &HttpRequest) -> Self::Future {
-        match render(&self) {
-            Ok(body) => future::ok(
-                HttpResponse::Ok()
-                    .content_type("text/html; charset=utf-8")
-                    .body(body),
-            ),
-            Err(err) => future::err(err),
-        }
+    fn respond_to(self, req: &HttpRequest) -> Self::Future {
+        future::ready(respond(self, req))
     }
 }
 
@@ -97,8 +364,94 @@ impl From<Nodes> for Document {
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Doctype {
+    Html,
+    Legacy { public: String, system: String },
+    Xml,
+    None,
+}
+
+impl Doctype {
+    pub fn legacy<P, S>(public: P, system: S) -> Self
+    where
+        P: Into<String>,
+        S: Into<String>,
+    {
+        Self::Legacy {
+            public: public.into(),
+            system: system.into(),
+        }
+    }
+
+    pub fn is_html(&self) -> bool {
+        match self {
+            Self::Html => true,
+            _ => false,
+        }
+    }
+
+    fn size_hint(&self) -> usize {
+        match self {
+            Self::Html => 15,
+            Self::Legacy { public, system } => 28 + public.len() + system.len(),
+            Self::Xml => 38,
+            Self::None => 0,
+        }
+    }
+}
+
+impl Default for Doctype {
+    fn default() -> Self {
+        Self::Html
+    }
+}
+
+fn child<'a>(nodes: &'a Nodes, tag: &str) -> Option<&'a Element> {
+    nodes
+        .iter()
+        .filter_map(Node::as_element)
+        .find(|element| element.tag() == tag)
+}
+
+fn child_or_insert<'a>(nodes: &'a mut Nodes, tag: &str, prepend: bool) -> &'a mut Element {
+    let index = nodes.iter().position(|node| is_tag(node, tag));
+    let index = match index {
+        Some(index) => index,
+        None if prepend => {
+            nodes.prepend(Element::new(tag));
+            0
+        }
+        None => {
+            nodes.append(Element::new(tag));
+            nodes.len() - 1
+        }
+    };
+
+    match nodes.get_mut(index) {
+        Some(Node::Element(element)) => element,
+        _ => unreachable!(),
+    }
+}
+
+fn is_tag(node: &Node, tag: &str) -> bool {
+    node.as_element()
+        .map_or(false, |element| element.tag() == tag)
+}
+
+fn is_meta(element: &Element, name: &str) -> bool {
+    element.tag() == "meta"
+        && element
+            .get_attr("name")
+            .and_then(Attribute::as_string)
+            .map_or(false, |value| value == name)
+}
+
 #[cfg(test)]
 mod tests {
+    use super::Doctype;
+    use crate::util::render::{render, render_with, RendererConfig};
     use crate::{Document, Element, Text};
 
     #[test]
@@ -122,4 +475,90 @@ mod tests {
 
         assert_eq!(text.as_text().unwrap().value(), "hello world");
     }
+
+    #[test]
+    fn test_document_doctype() {
+        let document = Document::new().with_node(Element::new("p"));
+
+        assert_eq!(render(&document).unwrap(), "<!DOCTYPE html><p></p>");
+        assert_eq!(
+            render(&document.clone().with_doctype(Doctype::None)).unwrap(),
+            "<p></p>"
+        );
+        assert_eq!(
+            render(&document.clone().with_doctype(Doctype::Xml)).unwrap(),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?><p></p>"
+        );
+        assert_eq!(
+            render(&document.clone().with_doctype(Doctype::legacy(
+                "-//W3C//DTD HTML 4.01//EN",
+                "http://www.w3.org/TR/html4/strict.dtd"
+            )))
+            .unwrap(),
+            "<!DOCTYPE html PUBLIC \"-//W3C//DTD HTML 4.01//EN\" \"http://www.w3.org/TR/html4/strict.dtd\"><p></p>"
+        );
+        assert_eq!(
+            render_with(
+                &document.with_doctype(Doctype::None),
+                &RendererConfig::pretty()
+            )
+            .unwrap(),
+            "<p></p>"
+        );
+    }
+
+    #[test]
+    fn test_document_doctype_xml() {
+        let document = Document::new().with_node(Element::new("svg"));
+        let config = RendererConfig::xml();
+
+        assert_eq!(
+            render_with(&document, &config).unwrap(),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?><svg />"
+        );
+        assert_eq!(
+            render_with(&document, &config.clone().declaration(false)).unwrap(),
+            "<svg />"
+        );
+        assert_eq!(
+            render_with(
+                &document.with_doctype(Doctype::legacy(
+                    "-//W3C//DTD XHTML 1.0 Strict//EN",
+                    "http://www.w3.org/TR/xhtml1/DTD/xhtml1-strict.dtd"
+                )),
+                &config
+            )
+            .unwrap(),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+             <!DOCTYPE html PUBLIC \"-//W3C//DTD XHTML 1.0 Strict//EN\" \"http://www.w3.org/TR/xhtml1/DTD/xhtml1-strict.dtd\">\
+             <svg />"
+        );
+    }
+
+    #[test]
+    fn test_document_metadata() {
+        let mut document = Document::new()
+            .with_node(Element::new("p").with_node("Hello"))
+            .with_lang("en")
+            .with_title("Hello   world")
+            .with_meta("description", "A greeting");
+
+        assert_eq!(document.lang(), Some("en"));
+        assert_eq!(document.title(), Some("Hello world"));
+        assert_eq!(document.meta("description"), Some("A greeting"));
+        assert_eq!(document.meta("author"), None);
+        assert!(document.body().is_none());
+
+        document
+            .set_title("Goodbye")
+            .set_meta("description", "A farewell");
+        document.body_mut().nodes_mut().append(Element::new("main"));
+
+        assert_eq!(
+            render(&document).unwrap(),
+            "<!DOCTYPE html><html lang=\"en\"><head><title>Goodbye</title>\
+             <meta name=\"description\" content=\"A farewell\" /></head>\
+             <body><p>Hello</p><main></main></body></html>"
+        );
+    }
 }
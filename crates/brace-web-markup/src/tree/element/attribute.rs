@@ -1,11 +1,20 @@
+use std::borrow::Cow;
+use std::fmt::Display;
+use std::str::FromStr;
+
 use indexmap::map::{Entry, IndexMap, IntoIter, Iter, IterMut};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use serde_json::Error as JsonError;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(untagged)]
 pub enum Attribute {
     String(String),
     Boolean(bool),
+    Number(f64),
+    Tokens(Vec<String>),
+    Url { url: String },
     None,
 }
 
@@ -66,6 +75,101 @@ impl Attribute {
         }
     }
 
+    pub fn number<T>(number: T) -> Self
+    where
+        T: Into<f64>,
+    {
+        Self::Number(number.into())
+    }
+
+    pub fn is_number(&self) -> bool {
+        match self {
+            Self::Number(_) => true,
+            _ => false,
+        }
+    }
+
+    pub fn as_number(&self) -> Option<&f64> {
+        match self {
+            Self::Number(number) => Some(number),
+            _ => None,
+        }
+    }
+
+    pub fn as_number_mut(&mut self) -> Option<&mut f64> {
+        match self {
+            Self::Number(number) => Some(number),
+            _ => None,
+        }
+    }
+
+    pub fn tokens<I, T>(tokens: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        Self::Tokens(tokens.into_iter().map(Into::into).collect())
+    }
+
+    pub fn is_tokens(&self) -> bool {
+        match self {
+            Self::Tokens(_) => true,
+            _ => false,
+        }
+    }
+
+    pub fn as_tokens(&self) -> Option<&Vec<String>> {
+        match self {
+            Self::Tokens(tokens) => Some(tokens),
+            _ => None,
+        }
+    }
+
+    pub fn as_tokens_mut(&mut self) -> Option<&mut Vec<String>> {
+        match self {
+            Self::Tokens(tokens) => Some(tokens),
+            _ => None,
+        }
+    }
+
+    pub fn url<T>(url: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self::Url { url: url.into() }
+    }
+
+    pub fn is_url(&self) -> bool {
+        match self {
+            Self::Url { .. } => true,
+            _ => false,
+        }
+    }
+
+    pub fn as_url(&self) -> Option<&String> {
+        match self {
+            Self::Url { url } => Some(url),
+            _ => None,
+        }
+    }
+
+    pub fn as_url_mut(&mut self) -> Option<&mut String> {
+        match self {
+            Self::Url { url } => Some(url),
+            _ => None,
+        }
+    }
+
+    pub fn to_value(&self) -> Option<Cow<'_, str>> {
+        match self {
+            Self::String(string) => Some(Cow::Borrowed(string)),
+            Self::Number(number) => Some(Cow::Owned(number.to_string())),
+            Self::Tokens(tokens) => Some(Cow::Owned(tokens.join(" "))),
+            Self::Url { url } => Some(Cow::Borrowed(url)),
+            Self::Boolean(_) | Self::None => None,
+        }
+    }
+
     pub const fn none() -> Self {
         Self::None
     }
@@ -102,6 +206,60 @@ impl From<bool> for Attribute {
     }
 }
 
+macro_rules! numbers {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Attribute {
+                fn from(from: $ty) -> Self {
+                    Self::Number(f64::from(from))
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! integers {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Attribute {
+                fn from(from: $ty) -> Self {
+                    Self::Number(from as f64)
+                }
+            }
+        )*
+    };
+}
+
+numbers!(i8, i16, i32, u8, u16, u32, f32, f64);
+
+// Numbers are stored as `f64`, so integers beyond 2^53 are rounded. Use
+// `set_data` or a string for identifiers that must round-trip exactly.
+integers!(i64, isize, u64, usize);
+
+impl From<Vec<&str>> for Attribute {
+    fn from(from: Vec<&str>) -> Self {
+        Self::tokens(from)
+    }
+}
+
+impl From<Vec<String>> for Attribute {
+    fn from(from: Vec<String>) -> Self {
+        Self::Tokens(from)
+    }
+}
+
+impl<T> From<Option<T>> for Attribute
+where
+    T: Into<Attribute>,
+{
+    fn from(from: Option<T>) -> Self {
+        match from {
+            Some(attr) => attr.into(),
+            None => Self::None,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 #[serde(transparent)]
 pub struct Attributes(IndexMap<String, Attribute>);
@@ -164,11 +322,267 @@ impl Attributes {
     pub fn iter_mut(&mut self) -> IterMut<'_, String, Attribute> {
         self.0.iter_mut()
     }
+
+    pub fn classes(&self) -> impl Iterator<Item = &str> + '_ {
+        let class = self.get("class");
+
+        class
+            .and_then(Attribute::as_string)
+            .into_iter()
+            .flat_map(|class| class.split_whitespace())
+            .chain(
+                class
+                    .and_then(Attribute::as_tokens)
+                    .into_iter()
+                    .flatten()
+                    .map(String::as_str),
+            )
+    }
+
+    pub fn has_class<T>(&self, class: T) -> bool
+    where
+        T: AsRef<str>,
+    {
+        let class = class.as_ref();
+
+        self.classes().any(|item| item == class)
+    }
+
+    pub fn add_class<T>(&mut self, class: T) -> &mut Self
+    where
+        T: AsRef<str>,
+    {
+        self.update_classes(|classes| {
+            for class in class.as_ref().split_whitespace() {
+                if !classes.iter().any(|item| item == class) {
+                    classes.push(class.to_owned());
+                }
+            }
+        })
+    }
+
+    pub fn remove_class<T>(&mut self, class: T) -> &mut Self
+    where
+        T: AsRef<str>,
+    {
+        if self.get("class").is_none() {
+            return self;
+        }
+
+        self.update_classes(|classes| {
+            for class in class.as_ref().split_whitespace() {
+                classes.retain(|item| item != class);
+            }
+        })
+    }
+
+    pub fn toggle_class<T>(&mut self, class: T) -> &mut Self
+    where
+        T: AsRef<str>,
+    {
+        self.update_classes(|classes| {
+            for class in class.as_ref().split_whitespace() {
+                match classes.iter().position(|item| item == class) {
+                    Some(index) => {
+                        classes.remove(index);
+                    }
+                    None => classes.push(class.to_owned()),
+                }
+            }
+        })
+    }
+
+    pub fn styles(&self) -> IndexMap<String, String> {
+        let mut styles = IndexMap::new();
+
+        if let Some(style) = self.get("style").and_then(Attribute::as_string) {
+            for declaration in style.split(';') {
+                let mut parts = declaration.splitn(2, ':');
+                let name = parts.next().unwrap_or_default().trim();
+                let value = parts.next().unwrap_or_default().trim();
+
+                if !name.is_empty() && !value.is_empty() {
+                    styles.insert(name.to_owned(), value.to_owned());
+                }
+            }
+        }
+
+        styles
+    }
+
+    pub fn get_style<K>(&self, name: K) -> Option<String>
+    where
+        K: AsRef<str>,
+    {
+        self.styles().shift_remove(name.as_ref())
+    }
+
+    pub fn set_style<K, V>(&mut self, name: K, value: V) -> &mut Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let mut styles = self.styles();
+
+        styles.insert(name.into(), value.into());
+        self.set_styles(styles)
+    }
+
+    pub fn unset_style<K>(&mut self, name: K) -> &mut Self
+    where
+        K: AsRef<str>,
+    {
+        if self.get("style").is_none() {
+            return self;
+        }
+
+        let mut styles = self.styles();
+
+        styles.shift_remove(name.as_ref());
+        self.set_styles(styles)
+    }
+
+    pub fn get_data<K, T>(&self, key: K) -> Option<T>
+    where
+        K: AsRef<str>,
+        T: FromStr,
+    {
+        self.get(prefixed("data-", key.as_ref()))
+            .and_then(Attribute::to_value)
+            .and_then(|value| value.parse().ok())
+    }
+
+    pub fn set_data<K, V>(&mut self, key: K, value: V) -> &mut Self
+    where
+        K: AsRef<str>,
+        V: Display,
+    {
+        self.set(prefixed("data-", key.as_ref()), value.to_string())
+    }
+
+    pub fn unset_data<K>(&mut self, key: K) -> &mut Self
+    where
+        K: AsRef<str>,
+    {
+        self.unset(prefixed("data-", key.as_ref()))
+    }
+
+    pub fn get_data_value<K, T>(&self, key: K) -> Option<T>
+    where
+        K: AsRef<str>,
+        T: DeserializeOwned,
+    {
+        self.get(prefixed("data-", key.as_ref()))
+            .and_then(Attribute::as_string)
+            .and_then(|value| serde_json::from_str(value).ok())
+    }
+
+    pub fn set_data_value<K, V>(&mut self, key: K, value: &V) -> Result<&mut Self, JsonError>
+    where
+        K: AsRef<str>,
+        V: Serialize,
+    {
+        let value = serde_json::to_string(value)?;
+
+        Ok(self.set(prefixed("data-", key.as_ref()), value))
+    }
+
+    pub fn dataset(&self) -> impl Iterator<Item = (&str, &Attribute)> + '_ {
+        self.iter()
+            .filter(|(key, _)| key.starts_with("data-"))
+            .map(|(key, attr)| (&key["data-".len()..], attr))
+    }
+
+    pub fn get_aria<K>(&self, key: K) -> Option<&str>
+    where
+        K: AsRef<str>,
+    {
+        self.get(prefixed("aria-", key.as_ref()))
+            .and_then(Attribute::as_string)
+            .map(String::as_str)
+    }
+
+    pub fn set_aria<K, V>(&mut self, key: K, value: V) -> &mut Self
+    where
+        K: AsRef<str>,
+        V: Display,
+    {
+        self.set(prefixed("aria-", key.as_ref()), value.to_string())
+    }
+
+    pub fn unset_aria<K>(&mut self, key: K) -> &mut Self
+    where
+        K: AsRef<str>,
+    {
+        self.unset(prefixed("aria-", key.as_ref()))
+    }
+
+    pub fn role(&self) -> Option<&str> {
+        self.get("role")
+            .and_then(Attribute::as_string)
+            .map(String::as_str)
+    }
+
+    pub fn set_role<T>(&mut self, role: T) -> &mut Self
+    where
+        T: Into<String>,
+    {
+        self.set("role", role.into())
+    }
+
+    fn update_classes<F>(&mut self, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut Vec<String>),
+    {
+        let mut classes = self.classes().map(str::to_owned).collect::<Vec<_>>();
+
+        f(&mut classes);
+
+        if classes.is_empty() {
+            self.unset("class")
+        } else if self.get("class").map_or(false, Attribute::is_tokens) {
+            self.set("class", classes)
+        } else {
+            self.set("class", classes.join(" "))
+        }
+    }
+
+    fn set_styles(&mut self, styles: IndexMap<String, String>) -> &mut Self {
+        if styles.is_empty() {
+            return self.unset("style");
+        }
+
+        let style = styles
+            .iter()
+            .map(|(name, value)| format!("{}: {}", name, value))
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        self.set("style", style)
+    }
+}
+
+fn prefixed(prefix: &str, key: &str) -> String {
+    if key.starts_with(prefix) {
+        key.to_owned()
+    } else {
+        format!("{}{}", prefix, key)
+    }
 }
 
 impl Extend<(String, Attribute)> for Attributes {
     fn extend<I: IntoIterator<Item = (String, Attribute)>>(&mut self, iter: I) {
-        self.0.extend(iter)
+        for (key, attr) in iter {
+            if key == "class" && self.get("class").is_some() {
+                if let Attribute::String(_) | Attribute::Tokens(_) = attr {
+                    self.add_class(attr.to_value().unwrap_or_default());
+
+                    continue;
+                }
+            }
+
+            self.0.insert(key, attr);
+        }
     }
 }
 
@@ -216,3 +630,165 @@ impl From<Vec<(&str, Attribute)>> for Attributes {
         attrs
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Attribute, Attributes};
+
+    #[test]
+    fn test_attributes_class() {
+        let mut attrs = Attributes::new();
+
+        attrs.add_class("one two").add_class("two three");
+
+        assert_eq!(
+            attrs.get("class"),
+            Some(&Attribute::string("one two three"))
+        );
+        assert!(attrs.has_class("two"));
+        assert!(!attrs.has_class("four"));
+
+        attrs
+            .remove_class("two")
+            .toggle_class("one")
+            .toggle_class("four");
+
+        assert_eq!(attrs.classes().collect::<Vec<_>>(), vec!["three", "four"]);
+
+        attrs.remove_class("three four");
+
+        assert!(attrs.get("class").is_none());
+
+        attrs.set("class", "one");
+        attrs.extend(vec![
+            ("class".to_owned(), Attribute::string("one two")),
+            ("id".to_owned(), Attribute::string("test")),
+        ]);
+
+        assert_eq!(attrs.get("class"), Some(&Attribute::string("one two")));
+        assert_eq!(attrs.get("id"), Some(&Attribute::string("test")));
+    }
+
+    #[test]
+    fn test_attribute_values() {
+        assert_eq!(Attribute::from(3), Attribute::Number(3.0));
+        assert_eq!(Attribute::from(1.5f32), Attribute::Number(1.5));
+        assert_eq!(
+            Attribute::from(vec!["a", "b"]),
+            Attribute::tokens(vec!["a", "b"])
+        );
+        assert_eq!(Attribute::from(Some("a")), Attribute::string("a"));
+        assert_eq!(Attribute::from(None::<u8>), Attribute::None);
+        assert_eq!(Attribute::from(12).to_value().unwrap(), "12");
+        assert_eq!(Attribute::from(0.25).to_value().unwrap(), "0.25");
+        assert_eq!(Attribute::from(vec!["a", "b"]).to_value().unwrap(), "a b");
+        assert_eq!(Attribute::url("/a?b=c").to_value().unwrap(), "/a?b=c");
+        assert_eq!(Attribute::from(true).to_value(), None);
+
+        let mut attrs = Attributes::new();
+
+        attrs.set("class", vec!["one", "two"]).add_class("three");
+
+        assert_eq!(
+            attrs.get("class"),
+            Some(&Attribute::tokens(vec!["one", "two", "three"]))
+        );
+
+        attrs.set("data-count", 2);
+
+        assert_eq!(attrs.get_data::<_, u8>("count"), Some(2));
+
+        assert!(Attribute::from(5usize).is_number());
+        assert_eq!(Attribute::from(5usize), Attribute::from(5));
+        assert_eq!(Attribute::from(-2i64).as_number(), Some(&-2.0));
+
+        attrs.set_data("id", 9_007_199_254_740_993u64);
+
+        assert_eq!(attrs.get_data::<_, u64>("id"), Some(9_007_199_254_740_993));
+    }
+
+    #[test]
+    fn test_attributes_style() {
+        let mut attrs = Attributes::new();
+
+        attrs.set("style", "color: red;; margin:0 auto ; invalid");
+
+        assert_eq!(attrs.get_style("color"), Some("red".to_owned()));
+        assert_eq!(attrs.get_style("margin"), Some("0 auto".to_owned()));
+        assert_eq!(attrs.styles().len(), 2);
+
+        attrs
+            .set_style("color", "blue")
+            .set_style("display", "none");
+
+        assert_eq!(
+            attrs.get("style"),
+            Some(&Attribute::string(
+                "color: blue; margin: 0 auto; display: none"
+            ))
+        );
+
+        attrs
+            .unset_style("color")
+            .unset_style("margin")
+            .unset_style("display");
+
+        assert!(attrs.get("style").is_none());
+    }
+
+    #[test]
+    fn test_attributes_data() {
+        let mut attrs = Attributes::new();
+
+        attrs
+            .set_data("user-id", 42)
+            .set_data("data-name", "alice")
+            .set("id", "test");
+
+        assert_eq!(attrs.get("data-user-id"), Some(&Attribute::string("42")));
+        assert_eq!(attrs.get_data::<_, u32>("user-id"), Some(42));
+        assert_eq!(attrs.get_data::<_, u32>("name"), None);
+        assert_eq!(
+            attrs.get_data::<_, String>("name"),
+            Some("alice".to_owned())
+        );
+        assert_eq!(
+            attrs.dataset().map(|(key, _)| key).collect::<Vec<_>>(),
+            vec!["user-id", "name"]
+        );
+
+        attrs.set_data_value("tags", &vec!["a", "b"]).unwrap();
+
+        assert_eq!(
+            attrs.get("data-tags"),
+            Some(&Attribute::string(r#"["a","b"]"#))
+        );
+        assert_eq!(
+            attrs.get_data_value::<_, Vec<String>>("tags"),
+            Some(vec!["a".to_owned(), "b".to_owned()])
+        );
+
+        attrs.unset_data("user-id").unset_data("tags");
+
+        assert_eq!(attrs.dataset().count(), 1);
+    }
+
+    #[test]
+    fn test_attributes_aria() {
+        let mut attrs = Attributes::new();
+
+        attrs
+            .set_role("button")
+            .set_aria("pressed", false)
+            .set_aria("aria-label", "Close");
+
+        assert_eq!(attrs.role(), Some("button"));
+        assert_eq!(attrs.get_aria("pressed"), Some("false"));
+        assert_eq!(attrs.get_aria("label"), Some("Close"));
+
+        attrs.unset_aria("pressed");
+
+        assert_eq!(attrs.get_aria("pressed"), None);
+        assert_eq!(attrs.len(), 2);
+    }
+}
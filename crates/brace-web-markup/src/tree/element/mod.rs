@@ -1,16 +1,31 @@
-use std::fmt::Write;
+use std::fmt::{Display, Write};
+use std::mem;
 use std::ops::{Index, IndexMut};
+use std::str::FromStr;
 
 use futures::future::{self, Ready};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use serde_json::Error as JsonError;
 
 use brace_web_core::{HttpRequest, HttpResponse, Responder};
 
-use crate::util::render::{render, Error, Render, Renderer, Result as RenderResult};
-use crate::{Attribute, Attributes, Node, Nodes};
+use crate::tree::visit::{
+    Ancestors, BreadthFirst, Descendants, Elements, Paths, Texts, Visit, VisitMut, Visitor,
+    VisitorMut,
+};
+use crate::util::escape::{
+    escape_attribute, escape_url, is_valid_attribute, is_valid_tag, is_void,
+};
+use crate::util::render::{render, Error, Frame, Layout, Render, Renderer, Result as RenderResult};
+use crate::util::selector::Error as SelectorError;
+use crate::util::stream::respond;
+use crate::{Attribute, Attributes, Node, Nodes, Raw, Text};
 
 pub mod attribute;
+pub mod namespace;
 pub mod tag;
+pub mod value;
 
 pub fn element<T>(tag: T) -> Element
 where
@@ -98,6 +113,187 @@ impl Element {
         self
     }
 
+    pub fn has_class<T>(&self, class: T) -> bool
+    where
+        T: AsRef<str>,
+    {
+        self.attrs.has_class(class)
+    }
+
+    pub fn add_class<T>(&mut self, class: T) -> &mut Self
+    where
+        T: AsRef<str>,
+    {
+        self.attrs.add_class(class);
+        self
+    }
+
+    pub fn remove_class<T>(&mut self, class: T) -> &mut Self
+    where
+        T: AsRef<str>,
+    {
+        self.attrs.remove_class(class);
+        self
+    }
+
+    pub fn toggle_class<T>(&mut self, class: T) -> &mut Self
+    where
+        T: AsRef<str>,
+    {
+        self.attrs.toggle_class(class);
+        self
+    }
+
+    pub fn with_class<T>(mut self, class: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        self.attrs.add_class(class);
+        self
+    }
+
+    pub fn get_style<K>(&self, name: K) -> Option<String>
+    where
+        K: AsRef<str>,
+    {
+        self.attrs.get_style(name)
+    }
+
+    pub fn set_style<K, V>(&mut self, name: K, value: V) -> &mut Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.attrs.set_style(name, value);
+        self
+    }
+
+    pub fn unset_style<K>(&mut self, name: K) -> &mut Self
+    where
+        K: AsRef<str>,
+    {
+        self.attrs.unset_style(name);
+        self
+    }
+
+    pub fn with_style<K, V>(mut self, name: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.attrs.set_style(name, value);
+        self
+    }
+
+    pub fn get_data<K, T>(&self, key: K) -> Option<T>
+    where
+        K: AsRef<str>,
+        T: FromStr,
+    {
+        self.attrs.get_data(key)
+    }
+
+    pub fn set_data<K, V>(&mut self, key: K, value: V) -> &mut Self
+    where
+        K: AsRef<str>,
+        V: Display,
+    {
+        self.attrs.set_data(key, value);
+        self
+    }
+
+    pub fn unset_data<K>(&mut self, key: K) -> &mut Self
+    where
+        K: AsRef<str>,
+    {
+        self.attrs.unset_data(key);
+        self
+    }
+
+    pub fn with_data<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: AsRef<str>,
+        V: Display,
+    {
+        self.attrs.set_data(key, value);
+        self
+    }
+
+    pub fn get_data_value<K, T>(&self, key: K) -> Option<T>
+    where
+        K: AsRef<str>,
+        T: DeserializeOwned,
+    {
+        self.attrs.get_data_value(key)
+    }
+
+    pub fn set_data_value<K, V>(&mut self, key: K, value: &V) -> Result<&mut Self, JsonError>
+    where
+        K: AsRef<str>,
+        V: Serialize,
+    {
+        self.attrs.set_data_value(key, value)?;
+
+        Ok(self)
+    }
+
+    pub fn dataset(&self) -> impl Iterator<Item = (&str, &Attribute)> + '_ {
+        self.attrs.dataset()
+    }
+
+    pub fn get_aria<K>(&self, key: K) -> Option<&str>
+    where
+        K: AsRef<str>,
+    {
+        self.attrs.get_aria(key)
+    }
+
+    pub fn set_aria<K, V>(&mut self, key: K, value: V) -> &mut Self
+    where
+        K: AsRef<str>,
+        V: Display,
+    {
+        self.attrs.set_aria(key, value);
+        self
+    }
+
+    pub fn unset_aria<K>(&mut self, key: K) -> &mut Self
+    where
+        K: AsRef<str>,
+    {
+        self.attrs.unset_aria(key);
+        self
+    }
+
+    pub fn with_aria<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: AsRef<str>,
+        V: Display,
+    {
+        self.attrs.set_aria(key, value);
+        self
+    }
+
+    pub fn role(&self) -> Option<&str> {
+        self.attrs.role()
+    }
+
+    pub fn set_role<T>(&mut self, role: T) -> &mut Self
+    where
+        T: Into<String>,
+    {
+        self.attrs.set_role(role);
+        self
+    }
+
+    pub fn with_role<T>(mut self, role: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.attrs.set_role(role);
+        self
+    }
+
     pub fn nodes(&self) -> &Nodes {
         &self.nodes
     }
@@ -129,57 +325,371 @@ impl Element {
         self.nodes.extend(nodes);
         self
     }
-}
 
-impl Render for Element {
-    fn render(&self, renderer: &mut Renderer) -> RenderResult {
+    pub fn clear(&mut self) -> &mut Self {
+        self.nodes.clear();
+        self
+    }
+
+    pub fn take_children(&mut self) -> Nodes {
+        mem::take(&mut self.nodes)
+    }
+
+    pub fn wrap(self, wrapper: Element) -> Element {
+        wrapper.with_node(self)
+    }
+
+    pub fn unwrap(mut self) -> Nodes {
+        self.take_children()
+    }
+
+    pub fn inner_html(&self) -> Result<String, Error> {
+        render(&self.nodes)
+    }
+
+    /// Replaces the children with a single `Raw` node holding the unparsed
+    /// markup, so its content is not seen by `text_content` or selectors.
+    pub fn set_inner_html<T>(&mut self, html: T) -> &mut Self
+    where
+        T: Into<Raw>,
+    {
+        self.nodes = Nodes::from(Node::raw(html));
+        self
+    }
+
+    pub fn text_content(&self) -> String {
+        self.nodes.texts().map(Text::value).collect()
+    }
+
+    pub fn set_text_content<T>(&mut self, text: T) -> &mut Self
+    where
+        T: Into<Text>,
+    {
+        self.nodes = Nodes::from(Node::text(text));
+        self
+    }
+
+    pub fn get_path(&self, path: &[usize]) -> Option<&Node> {
+        self.nodes.get_path(path)
+    }
+
+    pub fn get_path_mut(&mut self, path: &[usize]) -> Option<&mut Node> {
+        self.nodes.get_path_mut(path)
+    }
+
+    pub fn descendants(&self) -> Descendants<'_> {
+        self.nodes.descendants()
+    }
+
+    pub fn breadth_first(&self) -> BreadthFirst<'_> {
+        self.nodes.breadth_first()
+    }
+
+    pub fn paths(&self) -> Paths<'_> {
+        self.nodes.paths()
+    }
+
+    pub fn ancestors(&self, path: &[usize]) -> Ancestors<'_> {
+        self.nodes.ancestors(path)
+    }
+
+    pub fn elements(&self) -> Elements<'_> {
+        self.nodes.elements()
+    }
+
+    pub fn texts(&self) -> Texts<'_> {
+        self.nodes.texts()
+    }
+
+    pub fn visit<V>(&self, visitor: &mut V) -> Visit
+    where
+        V: Visitor,
+    {
+        self.nodes.visit(visitor)
+    }
+
+    pub fn visit_mut<V>(&mut self, visitor: &mut V) -> VisitMut
+    where
+        V: VisitorMut,
+    {
+        self.nodes.visit_mut(visitor)
+    }
+
+    pub fn select(&self, selector: &str) -> Result<Vec<&Element>, SelectorError> {
+        self.nodes.select(selector)
+    }
+
+    pub fn select_first(&self, selector: &str) -> Result<Option<&Element>, SelectorError> {
+        self.nodes.select_first(selector)
+    }
+
+    pub fn select_first_mut(
+        &mut self,
+        selector: &str,
+    ) -> Result<Option<&mut Element>, SelectorError> {
+        self.nodes.select_first_mut(selector)
+    }
+
+    pub fn select_mut<F>(&mut self, selector: &str, f: F) -> Result<usize, SelectorError>
+    where
+        F: FnMut(&mut Element),
+    {
+        self.nodes.select_mut(selector, f)
+    }
+
+    pub fn is_void(&self) -> bool {
+        is_void(self.tag())
+    }
+
+    pub fn prefix(&self) -> Option<&str> {
+        self.tag.find(':').map(|index| &self.tag[..index])
+    }
+
+    pub fn local_name(&self) -> &str {
+        match self.tag.find(':') {
+            Some(index) => &self.tag[index + 1..],
+            None => &self.tag,
+        }
+    }
+
+    pub fn namespace(&self) -> Option<&str> {
+        let attr = match self.prefix() {
+            Some(prefix) => self.attrs.get(format!("xmlns:{}", prefix)),
+            None => self.attrs.get("xmlns"),
+        };
+
+        attr.and_then(Attribute::as_string).map(String::as_str)
+    }
+
+    pub fn with_namespace<T>(self, namespace: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.with_attr("xmlns", namespace.into())
+    }
+
+    pub fn with_prefix<P, T>(self, prefix: P, namespace: T) -> Self
+    where
+        P: AsRef<str>,
+        T: Into<String>,
+    {
+        self.with_attr(format!("xmlns:{}", prefix.as_ref()), namespace.into())
+    }
+
+    pub(crate) fn is_self_closing(&self, renderer: &Renderer) -> bool {
+        if renderer.is_xml() {
+            self.nodes.is_empty()
+        } else {
+            self.is_void()
+        }
+    }
+
+    pub(crate) fn is_raw_text(&self, renderer: &Renderer) -> bool {
+        !renderer.is_xml() && renderer.config().is_raw_text(self.tag())
+    }
+
+    pub(crate) fn is_preformatted(&self, renderer: &Renderer) -> bool {
+        renderer.config().is_preformatted(self.tag())
+    }
+
+    pub(crate) fn layout(&self, renderer: &Renderer) -> Layout {
+        if !renderer.is_pretty() {
+            Layout::Compact
+        } else if self.is_preformatted(renderer)
+            || renderer.is_inline(self.tag())
+            || self.nodes.iter().all(|node| node.is_inline(renderer))
+        {
+            Layout::Inline
+        } else {
+            Layout::Block
+        }
+    }
+
+    pub(crate) fn render_open(&self, renderer: &mut Renderer) -> RenderResult {
+        if !is_valid_tag(self.tag()) {
+            return Err(Error::InvalidTag(self.tag().to_owned()));
+        }
+
         write!(renderer, "<{}", self.tag())?;
 
+        let xml = renderer.is_xml();
+        let minify = renderer.is_minify();
+
         for (key, val) in self.attrs() {
-            match val {
-                Attribute::String(string) => write!(renderer, " {}=\"{}\"", key, string)?,
+            if !is_valid_attribute(key) {
+                return Err(Error::InvalidAttribute(key.to_owned()));
+            }
+
+            let value = match val {
                 Attribute::Boolean(boolean) => {
-                    if *boolean {
+                    if *boolean && xml {
+                        write!(renderer, " {}=\"{}\"", key, key)?;
+                    } else if *boolean {
                         write!(renderer, " {}", key)?;
                     }
+
+                    continue;
                 }
-                _ => (),
+                Attribute::Url { url } => escape_url(url),
+                _ => match val.to_value() {
+                    Some(value) => value,
+                    None => continue,
+                },
+            };
+
+            if minify && value.is_empty() {
+                write!(renderer, " {}", key)?
+            } else if minify && is_unquoted(&value) {
+                write!(renderer, " {}={}", key, escape_attribute(&value))?
+            } else {
+                write!(renderer, " {}=\"{}\"", key, escape_attribute(&value))?
             }
         }
 
-        match self.tag() {
-            "area" | "base" | "br" | "col" | "command" | "embed" | "hr" | "img" | "input"
-            | "keygen" | "link" | "meta" | "param" | "source" | "track" | "wbr" => {
-                write!(renderer, " />")?;
-            }
-            _ => {
-                write!(renderer, ">")?;
+        if self.is_self_closing(renderer) && !minify {
+            write!(renderer, " />")?;
+        } else {
+            write!(renderer, ">")?;
+        }
+
+        Ok(())
+    }
 
-                for node in self.nodes() {
-                    node.render(renderer)?;
+    pub(crate) fn markup_size_hint(&self) -> usize {
+        let attrs = self
+            .attrs
+            .iter()
+            .map(|(key, val)| match val {
+                Attribute::String(string) | Attribute::Url { url: string } => {
+                    key.len() + string.len() + 4
                 }
+                Attribute::Tokens(tokens) => {
+                    key.len() + tokens.iter().map(|token| token.len() + 1).sum::<usize>() + 3
+                }
+                Attribute::Number(_) => key.len() + 8,
+                _ => key.len() + 1,
+            })
+            .sum::<usize>();
 
-                write!(renderer, "</{}>", self.tag())?;
-            }
+        self.tag.len() * 2 + attrs + 5
+    }
+
+    pub(crate) fn render_close(&self, renderer: &mut Renderer) -> RenderResult {
+        Ok(write!(renderer, "</{}>", self.tag())?)
+    }
+
+    pub(crate) fn is_block(&self) -> bool {
+        match self.tag() {
+            "html" | "head" | "body" | "base" | "link" | "meta" | "title" | "style" | "caption"
+            | "colgroup" | "col" | "thead" | "tbody" | "tfoot" | "tr" | "td" | "th" | "li"
+            | "dd" | "dt" | "legend" | "optgroup" | "option" | "summary" | "table" => true,
+            tag => closes_paragraph(tag),
         }
+    }
+
+    pub(crate) fn omits_end(&self, next: Option<&Node>, parent: Option<&Element>) -> bool {
+        let next_tag = next.and_then(Node::as_element).map(Element::tag);
+        let last = next.is_none();
+
+        match self.tag() {
+            "html" | "body" => !next.map_or(false, Node::is_comment),
+            "head" => next_tag.is_some(),
+            "li" => last || next_tag == Some("li"),
+            "dt" => match next_tag {
+                Some("dt") | Some("dd") => true,
+                _ => false,
+            },
+            "dd" => match next_tag {
+                Some("dt") | Some("dd") => true,
+                _ => last,
+            },
+            "rt" | "rp" => match next_tag {
+                Some("rt") | Some("rp") => true,
+                _ => last,
+            },
+            "optgroup" => last || next_tag == Some("optgroup"),
+            "option" => match next_tag {
+                Some("option") | Some("optgroup") => true,
+                _ => last,
+            },
+            "thead" => match next_tag {
+                Some("tbody") | Some("tfoot") => true,
+                _ => false,
+            },
+            "tbody" => match next_tag {
+                Some("tbody") | Some("tfoot") => true,
+                _ => last,
+            },
+            "tfoot" => last,
+            "tr" => last || next_tag == Some("tr"),
+            "td" | "th" => match next_tag {
+                Some("td") | Some("th") => true,
+                _ => last,
+            },
+            "p" => match next_tag {
+                Some(tag) => closes_paragraph(tag),
+                None => last && parent.map_or(true, |parent| !is_transparent(parent.tag())),
+            },
+            _ => false,
+        }
+    }
+}
+
+fn is_unquoted(value: &str) -> bool {
+    value.chars().all(|c| match c {
+        '"' | '\'' | '=' | '<' | '>' | '`' => false,
+        c => !c.is_ascii_whitespace(),
+    })
+}
+
+fn closes_paragraph(tag: &str) -> bool {
+    match tag {
+        "address" | "article" | "aside" | "blockquote" | "details" | "dialog" | "div" | "dl"
+        | "fieldset" | "figcaption" | "figure" | "footer" | "form" | "h1" | "h2" | "h3" | "h4"
+        | "h5" | "h6" | "header" | "hgroup" | "hr" | "main" | "menu" | "nav" | "ol" | "p"
+        | "pre" | "search" | "section" | "ul" => true,
+        _ => false,
+    }
+}
+
+fn is_transparent(tag: &str) -> bool {
+    match tag {
+        "a" | "audio" | "del" | "ins" | "map" | "noscript" | "video" => true,
+        tag => tag.contains('-'),
+    }
+}
+
+impl Render for Element {
+    fn render(&self, renderer: &mut Renderer) -> RenderResult {
+        renderer.open_node(true)?;
+        self.render_open(renderer)?;
+
+        if !self.is_self_closing(renderer) {
+            let layout = self.layout(renderer);
+            let scope = renderer.enter(layout, self.is_raw_text(renderer));
+
+            renderer.walk(&self.nodes, Some(self), Frame::new(layout, true, scope))?;
+            renderer.leave(layout, scope)?;
+
+            self.render_close(renderer)?;
+        }
+
+        renderer.close_node();
 
         Ok(())
     }
+
+    fn size_hint(&self) -> usize {
+        self.markup_size_hint() + self.nodes.size_hint()
+    }
 }
 
 impl Responder for Element {
     type Error = Error;
     type Future = Ready<Result<HttpResponse, Self::Error>>;
 
-    fn respond_to(self, _: &HttpRequest) -> Self::Future {
-        match render(&self) {
-            Ok(body) => future::ok(
-                HttpResponse::Ok()
-                    .content_type("text/html; charset=utf-8")
-                    .body(body),
-            ),
-            Err(err) => future::err(err),
-        }
+    fn respond_to(self, req: &HttpRequest) -> Self::Future {
+        future::ready(respond(self, req))
     }
 }
 
@@ -237,6 +747,8 @@ impl From<(&str, Attributes, Nodes)> for Element {
 
 #[cfg(test)]
 mod tests {
+    use crate::tree::element::namespace;
+    use crate::util::render::render;
     use crate::{Attribute, Element, Text};
 
     #[test]
@@ -288,6 +800,23 @@ mod tests {
         assert!(element.attrs().get("attr").unwrap().is_none());
     }
 
+    #[test]
+    fn test_element_attribute_values() {
+        let element = Element::new("a")
+            .with_attr("href", Attribute::url("/search?q=a b&c=\"d\""))
+            .with_attr("tabindex", 1)
+            .with_attr("data-ratio", 0.5)
+            .with_attr("class", vec!["one", "two"])
+            .with_attr("title", None::<&str>)
+            .with_attr("rel", Some("next"));
+
+        assert_eq!(
+            render(&element).unwrap(),
+            "<a href=\"/search?q=a%20b&amp;c=%22d%22\" tabindex=\"1\" data-ratio=\"0.5\" \
+             class=\"one two\" rel=\"next\"></a>"
+        );
+    }
+
     #[test]
     fn test_element_impl() {
         let element = Element::new("div")
@@ -349,4 +878,97 @@ mod tests {
         assert!(element["two"].is_string());
         assert_eq!(element["two"].as_string().unwrap(), "hello universe");
     }
+
+    #[test]
+    fn test_element_manipulation() {
+        let mut element = Element::new("p")
+            .with_node(Text::preserved("Hello "))
+            .with_node(Element::new("em").with_node("world"));
+
+        assert_eq!(element.text_content(), "Hello world");
+        assert_eq!(element.inner_html().unwrap(), "Hello <em>world</em>");
+
+        element.set_inner_html("<b>bold</b>");
+
+        assert_eq!(element.inner_html().unwrap(), "<b>bold</b>");
+        assert_eq!(element.nodes().len(), 1);
+        assert!(element.nodes().get(0).unwrap().is_raw());
+
+        element.set_text_content("a < b");
+
+        assert_eq!(element.inner_html().unwrap(), "a &lt; b");
+
+        let children = element.take_children();
+
+        assert_eq!(children.len(), 1);
+        assert!(element.nodes().is_empty());
+
+        let wrapper = element.with_nodes(children).wrap(Element::new("div"));
+
+        assert_eq!(render(&wrapper).unwrap(), "<div><p>a &lt; b</p></div>");
+        assert_eq!(render(&wrapper.unwrap()).unwrap(), "<p>a &lt; b</p>");
+        assert!(Element::new("ul")
+            .with_node(Element::new("li"))
+            .clear()
+            .nodes()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_element_class_style() {
+        let mut element = Element::new("div")
+            .with_class("card")
+            .with_style("color", "red")
+            .with_attrs(vec![("class".to_owned(), Attribute::string("wide"))]);
+
+        element.toggle_class("active").remove_class("card");
+
+        assert!(element.has_class("wide"));
+        assert!(!element.has_class("card"));
+        assert_eq!(element.get_style("color"), Some("red".to_owned()));
+        assert_eq!(
+            render(&element).unwrap(),
+            "<div class=\"wide active\" style=\"color: red\"></div>"
+        );
+    }
+
+    #[test]
+    fn test_element_data_aria() {
+        let mut element = Element::new("button")
+            .with_role("switch")
+            .with_aria("checked", true)
+            .with_data("count", 3);
+
+        element.set_data_value("meta", &(1, "one")).unwrap();
+
+        assert_eq!(element.get_data::<_, i32>("count"), Some(3));
+        assert_eq!(
+            element.get_data_value::<_, (i32, String)>("meta"),
+            Some((1, "one".to_owned()))
+        );
+        assert_eq!(element.dataset().count(), 2);
+        assert_eq!(
+            render(&element).unwrap(),
+            "<button role=\"switch\" aria-checked=\"true\" data-count=\"3\" \
+             data-meta=\"[1,&quot;one&quot;]\"></button>"
+        );
+    }
+
+    #[test]
+    fn test_element_namespace() {
+        let element = Element::new("svg").with_namespace(namespace::SVG);
+
+        assert_eq!(element.prefix(), None);
+        assert_eq!(element.local_name(), "svg");
+        assert_eq!(element.namespace(), Some(namespace::SVG));
+
+        let element = Element::new("m:math").with_prefix("m", namespace::MATHML);
+
+        assert_eq!(element.prefix(), Some("m"));
+        assert_eq!(element.local_name(), "math");
+        assert_eq!(element.namespace(), Some(namespace::MATHML));
+        assert_eq!(element["xmlns:m"].as_string().unwrap(), namespace::MATHML);
+
+        assert_eq!(Element::new("div").namespace(), None);
+    }
 }
@@ -1,31 +1,1026 @@
-use crate::Element;
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+
+use crate::tree::element::value::{
+    Autocapitalize, ButtonType, ContentEditable, CrossOrigin, Decoding, Dir, EnterKeyHint,
+    FetchPriority, FormEnctype, FormMethod, InputMode, InputType, Length, ListType, Loading,
+    Popover, Preload, ReferrerPolicy, Scope, Shape, TrackKind, Translate, Wrap,
+};
+use crate::util::render::{Render, Renderer, Result as RenderResult};
+use crate::{Attribute, Element, Node, Nodes};
 
 macro_rules! elements {
-    ( $($name:ident)* ) => {
+    ( $( $name:ident $marker:ident )* ) => {
+        pub mod html {
+            $(
+                #[derive(Clone, Copy, Debug, PartialEq)]
+                pub enum $marker {}
+            )*
+        }
+
         $(
             #[cfg_attr(tarpaulin, skip)]
-            pub fn $name() -> Element {
-                Element::new(stringify!($name))
+            pub fn $name() -> ElementBuilder<html::$marker> {
+                ElementBuilder::new(stringify!($name))
             }
         )*
     };
 }
 
-elements! {
-    a abbr address area article aside audio b base bdi bdo blockquote body br button canvas caption
-    cite code col colgroup data datalist dd del details dfn dialog div dl dt em embed fieldset
-    figcaption figure footer form h1 h2 h3 h4 h5 h6 head header hgroup hr html i iframe img input
-    ins kbd label legend li link main map mark menu menuitem meta meter nav noscript object ol
-    optgroup option output p param picture pre progress q rb rp rt rtc ruby s samp script section
-    select slot small source span strong style sub summary sup table tbody td template textarea
-    tfoot th thead time title tr track u ul var video wbr
+macro_rules! foreign_elements {
+    ( $module:ident $namespace:ident ; $root:ident $root_marker:ident $( $name:ident $marker:ident $tag:literal )* ) => {
+        pub mod $module {
+            use super::ElementBuilder;
+            use crate::tree::element::namespace;
+
+            #[derive(Clone, Copy, Debug, PartialEq)]
+            pub enum $root_marker {}
+
+            #[cfg_attr(tarpaulin, skip)]
+            pub fn $root() -> ElementBuilder<$root_marker> {
+                ElementBuilder::new(stringify!($root)).with_namespace(namespace::$namespace)
+            }
+
+            $(
+                #[derive(Clone, Copy, Debug, PartialEq)]
+                pub enum $marker {}
+
+                #[cfg_attr(tarpaulin, skip)]
+                pub fn $name() -> ElementBuilder<$marker> {
+                    ElementBuilder::new($tag)
+                }
+            )*
+        }
+    };
+}
+
+macro_rules! attributes {
+    ( $module:ident ; $( $marker:ident { $( $method:ident $key:literal $kind:ident ),* $(,)? } )* ) => {
+        $(
+            impl ElementBuilder<$module::$marker> {
+                $( attribute!($method $key $kind); )*
+            }
+        )*
+    };
+}
+
+macro_rules! attribute {
+    ($method:ident $key:literal String) => {
+        pub fn $method<V>(mut self, value: V) -> Self
+        where
+            V: Into<String>,
+        {
+            self.element.set_attr($key, value.into());
+            self
+        }
+    };
+    ($method:ident $key:literal Url) => {
+        pub fn $method<V>(mut self, value: V) -> Self
+        where
+            V: Into<String>,
+        {
+            self.element.set_attr($key, Attribute::url(value));
+            self
+        }
+    };
+    ($method:ident $key:literal Tokens) => {
+        pub fn $method<I, V>(mut self, value: I) -> Self
+        where
+            I: IntoIterator<Item = V>,
+            V: Into<String>,
+        {
+            self.element.set_attr($key, Attribute::tokens(value));
+            self
+        }
+    };
+    ($method:ident $key:literal Length) => {
+        pub fn $method<V>(mut self, value: V) -> Self
+        where
+            V: Length,
+        {
+            self.element.set_attr($key, value);
+            self
+        }
+    };
+    ($method:ident $key:literal TrueFalse) => {
+        pub fn $method(mut self, value: bool) -> Self {
+            self.element.set_attr($key, value.to_string());
+            self
+        }
+    };
+    ($method:ident $key:literal $kind:ident) => {
+        pub fn $method(mut self, value: $kind) -> Self {
+            self.element.set_attr($key, value);
+            self
+        }
+    };
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ElementBuilder<T> {
+    element: Element,
+    marker: PhantomData<T>,
+}
+
+impl<T> ElementBuilder<T> {
+    fn new(tag: &str) -> Self {
+        Self {
+            element: Element::new(tag),
+            marker: PhantomData,
+        }
+    }
+
+    pub fn into_element(self) -> Element {
+        self.element
+    }
+
+    pub fn with_namespace<N>(mut self, namespace: N) -> Self
+    where
+        N: Into<String>,
+    {
+        self.element.set_attr("xmlns", namespace.into());
+        self
+    }
+
+    pub fn with_attr<K, V>(mut self, key: K, attr: V) -> Self
+    where
+        K: Into<String>,
+        V: Into<Attribute>,
+    {
+        self.element.set_attr(key, attr);
+        self
+    }
+
+    pub fn with_attrs<A>(mut self, attrs: A) -> Self
+    where
+        A: IntoIterator<Item = (String, Attribute)>,
+    {
+        self.element.attrs_mut().extend(attrs);
+        self
+    }
+
+    pub fn with_node<N>(mut self, node: N) -> Self
+    where
+        N: Into<Node>,
+    {
+        self.element.nodes_mut().append(node.into());
+        self
+    }
+
+    pub fn with_nodes<N>(mut self, nodes: N) -> Self
+    where
+        N: IntoIterator<Item = Node>,
+    {
+        self.element.nodes_mut().extend(nodes);
+        self
+    }
+
+    pub fn with_prefix<P, N>(mut self, prefix: P, namespace: N) -> Self
+    where
+        P: AsRef<str>,
+        N: Into<String>,
+    {
+        self.element = self.element.with_prefix(prefix, namespace);
+        self
+    }
+
+    pub fn with_class<C>(mut self, class: C) -> Self
+    where
+        C: AsRef<str>,
+    {
+        self.element.add_class(class);
+        self
+    }
+
+    pub fn with_style<K, V>(mut self, name: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.element.set_style(name, value);
+        self
+    }
+
+    pub fn with_data<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: AsRef<str>,
+        V: Display,
+    {
+        self.element.set_data(key, value);
+        self
+    }
+
+    pub fn with_aria<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: AsRef<str>,
+        V: Display,
+    {
+        self.element.set_aria(key, value);
+        self
+    }
+
+    pub fn with_role<R>(mut self, role: R) -> Self
+    where
+        R: Into<String>,
+    {
+        self.element.set_role(role);
+        self
+    }
+
+    pub fn wrap(self, wrapper: Element) -> Element {
+        self.element.wrap(wrapper)
+    }
+
+    pub fn unwrap(self) -> Nodes {
+        self.element.unwrap()
+    }
+
+    attribute!(accesskey "accesskey" String);
+    attribute!(autocapitalize "autocapitalize" Autocapitalize);
+    attribute!(autofocus "autofocus" bool);
+    attribute!(contenteditable "contenteditable" ContentEditable);
+    attribute!(dir "dir" Dir);
+    attribute!(draggable "draggable" TrueFalse);
+    attribute!(enterkeyhint "enterkeyhint" EnterKeyHint);
+    attribute!(hidden "hidden" bool);
+    attribute!(id "id" String);
+    attribute!(inert "inert" bool);
+    attribute!(inputmode "inputmode" InputMode);
+    attribute!(is "is" String);
+    attribute!(itemid "itemid" Url);
+    attribute!(itemprop "itemprop" Tokens);
+    attribute!(itemref "itemref" Tokens);
+    attribute!(itemscope "itemscope" bool);
+    attribute!(itemtype "itemtype" Tokens);
+    attribute!(lang "lang" String);
+    attribute!(nonce "nonce" String);
+    attribute!(popover "popover" Popover);
+    attribute!(slot "slot" String);
+    attribute!(spellcheck "spellcheck" TrueFalse);
+    attribute!(tabindex "tabindex" i32);
+    attribute!(title "title" String);
+    attribute!(translate "translate" Translate);
+}
+
+impl<T> Deref for ElementBuilder<T> {
+    type Target = Element;
+
+    fn deref(&self) -> &Self::Target {
+        &self.element
+    }
+}
+
+impl<T> DerefMut for ElementBuilder<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.element
+    }
+}
+
+impl<T> Render for ElementBuilder<T> {
+    fn render(&self, renderer: &mut Renderer) -> RenderResult {
+        self.element.render(renderer)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.element.size_hint()
+    }
+}
+
+impl<T> From<ElementBuilder<T>> for Element {
+    fn from(from: ElementBuilder<T>) -> Self {
+        from.element
+    }
+}
+
+impl<T> From<ElementBuilder<T>> for Node {
+    fn from(from: ElementBuilder<T>) -> Self {
+        Self::element(from.element)
+    }
+}
+
+impl<T> From<ElementBuilder<T>> for Nodes {
+    fn from(from: ElementBuilder<T>) -> Self {
+        Self::from(from.element)
+    }
 }
 
 elements! {
-    path circle ellipse line polygon polyline rect image
+    a A abbr Abbr address Address area Area article Article aside Aside audio Audio b B base Base
+    bdi Bdi bdo Bdo blockquote Blockquote body Body br Br button Button canvas Canvas
+    caption Caption cite Cite code Code col Col colgroup Colgroup data Data datalist Datalist
+    dd Dd del Del details Details dfn Dfn dialog Dialog div Div dl Dl dt Dt em Em embed Embed
+    fieldset Fieldset figcaption Figcaption figure Figure footer Footer form Form h1 H1 h2 H2
+    h3 H3 h4 H4 h5 H5 h6 H6 head Head header Header hgroup Hgroup hr Hr html Html i I
+    iframe Iframe img Img input Input ins Ins kbd Kbd label Label legend Legend li Li link Link
+    main Main map Map mark Mark menu Menu meta Meta meter Meter nav Nav
+    noscript Noscript object Object ol Ol optgroup Optgroup option Option output Output p P
+    picture Picture pre Pre progress Progress q Q rp Rp rt Rt
+    ruby Ruby s S samp Samp script Script search Search section Section select Select slot Slot
+    small Small
+    source Source span Span strong Strong style Style sub Sub summary Summary sup Sup
+    table Table tbody Tbody td Td template Template textarea Textarea tfoot Tfoot th Th
+    thead Thead time Time title Title tr Tr track Track u U ul Ul var Var video Video wbr Wbr
+}
+
+attributes! {
+    html;
+
+    A {
+        href "href" Url,
+        target "target" String,
+        download "download" String,
+        ping "ping" Tokens,
+        rel "rel" Tokens,
+        hreflang "hreflang" String,
+        type_ "type" String,
+        referrerpolicy "referrerpolicy" ReferrerPolicy,
+    }
+
+    Area {
+        alt "alt" String,
+        coords "coords" String,
+        shape "shape" Shape,
+        href "href" Url,
+        target "target" String,
+        download "download" String,
+        ping "ping" Tokens,
+        rel "rel" Tokens,
+        referrerpolicy "referrerpolicy" ReferrerPolicy,
+    }
+
+    Audio {
+        src "src" Url,
+        crossorigin "crossorigin" CrossOrigin,
+        preload "preload" Preload,
+        autoplay "autoplay" bool,
+        loop_ "loop" bool,
+        muted "muted" bool,
+        controls "controls" bool,
+    }
+
+    Base {
+        href "href" Url,
+        target "target" String,
+    }
+
+    Blockquote {
+        cite "cite" Url,
+    }
+
+    Button {
+        disabled "disabled" bool,
+        form "form" String,
+        formaction "formaction" Url,
+        formenctype "formenctype" FormEnctype,
+        formmethod "formmethod" FormMethod,
+        formnovalidate "formnovalidate" bool,
+        formtarget "formtarget" String,
+        name "name" String,
+        popovertarget "popovertarget" String,
+        type_ "type" ButtonType,
+        value "value" String,
+    }
+
+    Canvas {
+        width "width" u32,
+        height "height" u32,
+    }
+
+    Col {
+        span "span" u32,
+    }
+
+    Colgroup {
+        span "span" u32,
+    }
+
+    Data {
+        value "value" String,
+    }
+
+    Del {
+        cite "cite" Url,
+        datetime "datetime" String,
+    }
+
+    Details {
+        open "open" bool,
+        name "name" String,
+    }
+
+    Dialog {
+        open "open" bool,
+    }
+
+    Embed {
+        src "src" Url,
+        type_ "type" String,
+        width "width" u32,
+        height "height" u32,
+    }
+
+    Fieldset {
+        disabled "disabled" bool,
+        form "form" String,
+        name "name" String,
+    }
+
+    Form {
+        accept_charset "accept-charset" String,
+        action "action" Url,
+        autocomplete "autocomplete" String,
+        enctype "enctype" FormEnctype,
+        method "method" FormMethod,
+        name "name" String,
+        novalidate "novalidate" bool,
+        target "target" String,
+        rel "rel" Tokens,
+    }
+
+    Iframe {
+        src "src" Url,
+        srcdoc "srcdoc" String,
+        name "name" String,
+        sandbox "sandbox" Tokens,
+        allow "allow" String,
+        allowfullscreen "allowfullscreen" bool,
+        width "width" u32,
+        height "height" u32,
+        referrerpolicy "referrerpolicy" ReferrerPolicy,
+        loading "loading" Loading,
+    }
+
+    Img {
+        alt "alt" String,
+        src "src" Url,
+        srcset "srcset" String,
+        sizes "sizes" String,
+        crossorigin "crossorigin" CrossOrigin,
+        usemap "usemap" String,
+        ismap "ismap" bool,
+        width "width" u32,
+        height "height" u32,
+        referrerpolicy "referrerpolicy" ReferrerPolicy,
+        decoding "decoding" Decoding,
+        loading "loading" Loading,
+        fetchpriority "fetchpriority" FetchPriority,
+    }
+
+    Input {
+        accept "accept" String,
+        alt "alt" String,
+        autocomplete "autocomplete" String,
+        checked "checked" bool,
+        dirname "dirname" String,
+        disabled "disabled" bool,
+        form "form" String,
+        formaction "formaction" Url,
+        formenctype "formenctype" FormEnctype,
+        formmethod "formmethod" FormMethod,
+        formnovalidate "formnovalidate" bool,
+        formtarget "formtarget" String,
+        height "height" u32,
+        list "list" String,
+        max "max" String,
+        maxlength "maxlength" u32,
+        min "min" String,
+        minlength "minlength" u32,
+        multiple "multiple" bool,
+        name "name" String,
+        pattern "pattern" String,
+        placeholder "placeholder" String,
+        popovertarget "popovertarget" String,
+        readonly "readonly" bool,
+        required "required" bool,
+        size "size" u32,
+        src "src" Url,
+        step "step" String,
+        type_ "type" InputType,
+        value "value" String,
+        width "width" u32,
+    }
+
+    Ins {
+        cite "cite" Url,
+        datetime "datetime" String,
+    }
+
+    Label {
+        for_ "for" String,
+    }
+
+    Li {
+        value "value" i32,
+    }
+
+    Link {
+        href "href" Url,
+        crossorigin "crossorigin" CrossOrigin,
+        rel "rel" Tokens,
+        media "media" String,
+        integrity "integrity" String,
+        hreflang "hreflang" String,
+        type_ "type" String,
+        referrerpolicy "referrerpolicy" ReferrerPolicy,
+        sizes "sizes" Tokens,
+        as_ "as" String,
+        blocking "blocking" Tokens,
+        disabled "disabled" bool,
+        fetchpriority "fetchpriority" FetchPriority,
+    }
+
+    Map {
+        name "name" String,
+    }
+
+    Meta {
+        name "name" String,
+        http_equiv "http-equiv" String,
+        content "content" String,
+        charset "charset" String,
+        media "media" String,
+    }
+
+    Meter {
+        value "value" f64,
+        min "min" f64,
+        max "max" f64,
+        low "low" f64,
+        high "high" f64,
+        optimum "optimum" f64,
+    }
+
+    Object {
+        data "data" Url,
+        type_ "type" String,
+        name "name" String,
+        form "form" String,
+        width "width" u32,
+        height "height" u32,
+    }
+
+    Ol {
+        reversed "reversed" bool,
+        start "start" i32,
+        type_ "type" ListType,
+    }
+
+    Optgroup {
+        disabled "disabled" bool,
+        label "label" String,
+    }
+
+    Option {
+        disabled "disabled" bool,
+        label "label" String,
+        selected "selected" bool,
+        value "value" String,
+    }
+
+    Output {
+        for_ "for" Tokens,
+        form "form" String,
+        name "name" String,
+    }
+
+    Progress {
+        value "value" f64,
+        max "max" f64,
+    }
+
+    Q {
+        cite "cite" Url,
+    }
+
+    Script {
+        src "src" Url,
+        type_ "type" String,
+        nomodule "nomodule" bool,
+        async_ "async" bool,
+        defer "defer" bool,
+        crossorigin "crossorigin" CrossOrigin,
+        integrity "integrity" String,
+        referrerpolicy "referrerpolicy" ReferrerPolicy,
+        blocking "blocking" Tokens,
+        fetchpriority "fetchpriority" FetchPriority,
+    }
+
+    Select {
+        autocomplete "autocomplete" String,
+        disabled "disabled" bool,
+        form "form" String,
+        multiple "multiple" bool,
+        name "name" String,
+        required "required" bool,
+        size "size" u32,
+    }
+
+    Slot {
+        name "name" String,
+    }
+
+    Source {
+        type_ "type" String,
+        media "media" String,
+        src "src" Url,
+        srcset "srcset" String,
+        sizes "sizes" String,
+        width "width" u32,
+        height "height" u32,
+    }
+
+    Style {
+        media "media" String,
+        blocking "blocking" Tokens,
+    }
+
+    Td {
+        colspan "colspan" u32,
+        rowspan "rowspan" u32,
+        headers "headers" Tokens,
+    }
+
+    Template {
+        shadowrootmode "shadowrootmode" String,
+    }
+
+    Textarea {
+        autocomplete "autocomplete" String,
+        cols "cols" u32,
+        dirname "dirname" String,
+        disabled "disabled" bool,
+        form "form" String,
+        maxlength "maxlength" u32,
+        minlength "minlength" u32,
+        name "name" String,
+        placeholder "placeholder" String,
+        readonly "readonly" bool,
+        required "required" bool,
+        rows "rows" u32,
+        wrap_ "wrap" Wrap,
+    }
+
+    Th {
+        colspan "colspan" u32,
+        rowspan "rowspan" u32,
+        headers "headers" Tokens,
+        scope "scope" Scope,
+        abbr "abbr" String,
+    }
+
+    Time {
+        datetime "datetime" String,
+    }
+
+    Track {
+        default "default" bool,
+        kind "kind" TrackKind,
+        label "label" String,
+        src "src" Url,
+        srclang "srclang" String,
+    }
+
+    Video {
+        src "src" Url,
+        crossorigin "crossorigin" CrossOrigin,
+        poster "poster" Url,
+        preload "preload" Preload,
+        autoplay "autoplay" bool,
+        playsinline "playsinline" bool,
+        loop_ "loop" bool,
+        muted "muted" bool,
+        controls "controls" bool,
+        width "width" u32,
+        height "height" u32,
+    }
+}
+
+foreign_elements! {
+    svg SVG;
+
+    svg Svg
+    a A "a"
+    animate Animate "animate"
+    animate_motion AnimateMotion "animateMotion"
+    animate_transform AnimateTransform "animateTransform"
+    circle Circle "circle"
+    clip_path ClipPath "clipPath"
+    defs Defs "defs"
+    desc Desc "desc"
+    discard Discard "discard"
+    ellipse Ellipse "ellipse"
+    fe_blend FeBlend "feBlend"
+    fe_color_matrix FeColorMatrix "feColorMatrix"
+    fe_component_transfer FeComponentTransfer "feComponentTransfer"
+    fe_composite FeComposite "feComposite"
+    fe_convolve_matrix FeConvolveMatrix "feConvolveMatrix"
+    fe_diffuse_lighting FeDiffuseLighting "feDiffuseLighting"
+    fe_displacement_map FeDisplacementMap "feDisplacementMap"
+    fe_distant_light FeDistantLight "feDistantLight"
+    fe_drop_shadow FeDropShadow "feDropShadow"
+    fe_flood FeFlood "feFlood"
+    fe_func_a FeFuncA "feFuncA"
+    fe_func_b FeFuncB "feFuncB"
+    fe_func_g FeFuncG "feFuncG"
+    fe_func_r FeFuncR "feFuncR"
+    fe_gaussian_blur FeGaussianBlur "feGaussianBlur"
+    fe_image FeImage "feImage"
+    fe_merge FeMerge "feMerge"
+    fe_merge_node FeMergeNode "feMergeNode"
+    fe_morphology FeMorphology "feMorphology"
+    fe_offset FeOffset "feOffset"
+    fe_point_light FePointLight "fePointLight"
+    fe_specular_lighting FeSpecularLighting "feSpecularLighting"
+    fe_spot_light FeSpotLight "feSpotLight"
+    fe_tile FeTile "feTile"
+    fe_turbulence FeTurbulence "feTurbulence"
+    filter Filter "filter"
+    foreign_object ForeignObject "foreignObject"
+    g G "g"
+    image Image "image"
+    line Line "line"
+    linear_gradient LinearGradient "linearGradient"
+    marker Marker "marker"
+    mask Mask "mask"
+    metadata Metadata "metadata"
+    mpath Mpath "mpath"
+    path Path "path"
+    pattern Pattern "pattern"
+    polygon Polygon "polygon"
+    polyline Polyline "polyline"
+    radial_gradient RadialGradient "radialGradient"
+    rect Rect "rect"
+    script Script "script"
+    set Set "set"
+    stop Stop "stop"
+    style Style "style"
+    switch_ Switch "switch"
+    symbol Symbol "symbol"
+    text Text "text"
+    text_path TextPath "textPath"
+    title Title "title"
+    tspan Tspan "tspan"
+    use_ Use "use"
+    view View "view"
 }
 
-#[cfg_attr(tarpaulin, skip)]
-pub fn svg() -> Element {
-    Element::new("svg").with_attr("xmlns", "http://www.w3.org/2000/svg")
+attributes! {
+    svg;
+
+    Svg {
+        view_box "viewBox" String,
+        preserve_aspect_ratio "preserveAspectRatio" String,
+        x "x" Length,
+        y "y" Length,
+        width "width" Length,
+        height "height" Length,
+    }
+
+    A {
+        href "href" Url,
+        target "target" String,
+    }
+
+    Circle {
+        cx "cx" Length,
+        cy "cy" Length,
+        r "r" Length,
+    }
+
+    Ellipse {
+        cx "cx" Length,
+        cy "cy" Length,
+        rx "rx" Length,
+        ry "ry" Length,
+    }
+
+    Discard {
+        begin "begin" String,
+        href "href" Url,
+    }
+
+    Image {
+        href "href" Url,
+        x "x" Length,
+        y "y" Length,
+        width "width" Length,
+        height "height" Length,
+        preserve_aspect_ratio "preserveAspectRatio" String,
+    }
+
+    Line {
+        x1 "x1" Length,
+        y1 "y1" Length,
+        x2 "x2" Length,
+        y2 "y2" Length,
+    }
+
+    LinearGradient {
+        x1 "x1" Length,
+        y1 "y1" Length,
+        x2 "x2" Length,
+        y2 "y2" Length,
+        gradient_units "gradientUnits" String,
+        gradient_transform "gradientTransform" String,
+        href "href" Url,
+    }
+
+    Path {
+        d "d" String,
+        path_length "pathLength" f64,
+    }
+
+    Polygon {
+        points "points" String,
+    }
+
+    Polyline {
+        points "points" String,
+    }
+
+    RadialGradient {
+        cx "cx" Length,
+        cy "cy" Length,
+        r "r" Length,
+        fx "fx" Length,
+        fy "fy" Length,
+        gradient_units "gradientUnits" String,
+        gradient_transform "gradientTransform" String,
+        href "href" Url,
+    }
+
+    Rect {
+        x "x" Length,
+        y "y" Length,
+        width "width" Length,
+        height "height" Length,
+        rx "rx" Length,
+        ry "ry" Length,
+    }
+
+    Stop {
+        offset "offset" Length,
+    }
+
+    Symbol {
+        view_box "viewBox" String,
+        preserve_aspect_ratio "preserveAspectRatio" String,
+    }
+
+    Text {
+        x "x" Length,
+        y "y" Length,
+        dx "dx" Length,
+        dy "dy" Length,
+    }
+
+    Use {
+        href "href" Url,
+        x "x" Length,
+        y "y" Length,
+        width "width" Length,
+        height "height" Length,
+    }
+}
+
+foreign_elements! {
+    mathml MATHML;
+
+    math Math
+    annotation Annotation "annotation"
+    annotation_xml AnnotationXml "annotation-xml"
+    maction Maction "maction"
+    merror Merror "merror"
+    mfrac Mfrac "mfrac"
+    mi Mi "mi"
+    mmultiscripts Mmultiscripts "mmultiscripts"
+    mn Mn "mn"
+    mo Mo "mo"
+    mover Mover "mover"
+    mpadded Mpadded "mpadded"
+    mphantom Mphantom "mphantom"
+    mprescripts Mprescripts "mprescripts"
+    mroot Mroot "mroot"
+    mrow Mrow "mrow"
+    ms Ms "ms"
+    mspace Mspace "mspace"
+    msqrt Msqrt "msqrt"
+    mstyle Mstyle "mstyle"
+    msub Msub "msub"
+    msubsup Msubsup "msubsup"
+    msup Msup "msup"
+    mtable Mtable "mtable"
+    mtd Mtd "mtd"
+    mtext Mtext "mtext"
+    mtr Mtr "mtr"
+    munder Munder "munder"
+    munderover Munderover "munderover"
+    semantics Semantics "semantics"
+}
+
+attributes! {
+    mathml;
+
+    Math {
+        display "display" String,
+    }
+
+    Mfrac {
+        linethickness "linethickness" Length,
+    }
+
+    Mo {
+        form "form" String,
+        stretchy "stretchy" TrueFalse,
+        symmetric "symmetric" TrueFalse,
+        largeop "largeop" TrueFalse,
+        movablelimits "movablelimits" TrueFalse,
+    }
+
+    Mtd {
+        columnspan "columnspan" u32,
+        rowspan "rowspan" u32,
+    }
+}
+
+pub use self::mathml::math;
+pub use self::svg::{circle, ellipse, image, line, path, polygon, polyline, rect, svg};
+
+#[cfg(test)]
+mod tests {
+    use super::{a, div, img, input, math, mathml, ol, svg};
+    use crate::tree::element::value::{Dir, InputType, ListType};
+    use crate::util::render::render;
+    use crate::{Element, Node};
+
+    #[test]
+    fn test_element_builder() {
+        let element = div()
+            .id("main")
+            .with_class("one")
+            .with_class("two")
+            .with_role("main")
+            .with_style("color", "red")
+            .dir(Dir::Rtl)
+            .hidden(true)
+            .draggable(false)
+            .tabindex(-1)
+            .with_node(a().href("/about us").rel(vec!["next", "noopener"]))
+            .with_node(input().type_(InputType::Checkbox).checked(true))
+            .with_node(img().src("/logo.png").alt("Logo").width(64))
+            .with_node(ol().type_(ListType::UpperRoman).start(3));
+
+        assert_eq!(element.tag(), "div");
+        assert_eq!(element.role(), Some("main"));
+        assert!(element.has_class("two"));
+        assert_eq!(
+            render(&element).unwrap(),
+            "<div id=\"main\" class=\"one two\" role=\"main\" style=\"color: red\" dir=\"rtl\" \
+             hidden draggable=\"false\" \
+             tabindex=\"-1\"><a href=\"/about%20us\" rel=\"next noopener\"></a>\
+             <input type=\"checkbox\" checked /><img src=\"/logo.png\" alt=\"Logo\" width=\"64\" />\
+             <ol type=\"I\" start=\"3\"></ol></div>"
+        );
+
+        let node = Node::from(input().name("q"));
+
+        assert_eq!(
+            node.as_element(),
+            Some(&Element::new("input").with_attr("name", "q"))
+        );
+        assert_eq!(Element::from(a()), Element::new("a"));
+        assert_eq!(
+            a().wrap(Element::new("li")),
+            Element::new("li").with_node(Element::new("a"))
+        );
+    }
+
+    #[test]
+    fn test_foreign_builder() {
+        let element = svg()
+            .view_box("0 0 10 10")
+            .with_node(
+                svg::defs().with_node(
+                    svg::linear_gradient()
+                        .id("fade")
+                        .with_node(svg::stop().offset("0%")),
+                ),
+            )
+            .with_node(svg::use_().href("#shape").x(1).y(2.5))
+            .with_node(svg::text().x(0).with_node("Hello"))
+            .with_node(svg::discard().begin("2s").href("#shape"));
+
+        assert_eq!(
+            render(&element).unwrap(),
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 10 10\"><defs>\
+             <linearGradient id=\"fade\"><stop offset=\"0%\"></stop></linearGradient></defs>\
+             <use href=\"#shape\" x=\"1\" y=\"2.5\"></use><text x=\"0\">Hello</text>\
+             <discard begin=\"2s\" href=\"#shape\"></discard></svg>"
+        );
+        assert_eq!(
+            render(&math().with_node(mathml::annotation_xml())).unwrap(),
+            "<math xmlns=\"http://www.w3.org/1998/Math/MathML\"><annotation-xml></annotation-xml></math>"
+        );
+    }
 }
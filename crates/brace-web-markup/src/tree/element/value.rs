@@ -0,0 +1,215 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use crate::Attribute;
+
+macro_rules! values {
+    ( $( $name:ident { $( $variant:ident => $value:literal ),* $(,)? } )* ) => {
+        $(
+            #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+            pub enum $name {
+                $( $variant ),*
+            }
+
+            impl $name {
+                pub fn as_str(&self) -> &'static str {
+                    match self {
+                        $( Self::$variant => $value ),*
+                    }
+                }
+            }
+
+            impl Display for $name {
+                fn fmt(&self, f: &mut Formatter) -> FmtResult {
+                    f.write_str(self.as_str())
+                }
+            }
+
+            impl From<$name> for Attribute {
+                fn from(from: $name) -> Self {
+                    Self::string(from.as_str())
+                }
+            }
+        )*
+    };
+}
+
+values! {
+    Autocapitalize {
+        Off => "off",
+        None => "none",
+        On => "on",
+        Sentences => "sentences",
+        Words => "words",
+        Characters => "characters",
+    }
+
+    ButtonType {
+        Button => "button",
+        Reset => "reset",
+        Submit => "submit",
+    }
+
+    ContentEditable {
+        True => "true",
+        False => "false",
+        PlaintextOnly => "plaintext-only",
+    }
+
+    CrossOrigin {
+        Anonymous => "anonymous",
+        UseCredentials => "use-credentials",
+    }
+
+    Decoding {
+        Sync => "sync",
+        Async => "async",
+        Auto => "auto",
+    }
+
+    Dir {
+        Ltr => "ltr",
+        Rtl => "rtl",
+        Auto => "auto",
+    }
+
+    EnterKeyHint {
+        Enter => "enter",
+        Done => "done",
+        Go => "go",
+        Next => "next",
+        Previous => "previous",
+        Search => "search",
+        Send => "send",
+    }
+
+    FetchPriority {
+        High => "high",
+        Low => "low",
+        Auto => "auto",
+    }
+
+    FormEnctype {
+        UrlEncoded => "application/x-www-form-urlencoded",
+        Multipart => "multipart/form-data",
+        Plain => "text/plain",
+    }
+
+    FormMethod {
+        Get => "get",
+        Post => "post",
+        Dialog => "dialog",
+    }
+
+    InputMode {
+        None => "none",
+        Text => "text",
+        Decimal => "decimal",
+        Numeric => "numeric",
+        Tel => "tel",
+        Search => "search",
+        Email => "email",
+        Url => "url",
+    }
+
+    InputType {
+        Button => "button",
+        Checkbox => "checkbox",
+        Color => "color",
+        Date => "date",
+        DatetimeLocal => "datetime-local",
+        Email => "email",
+        File => "file",
+        Hidden => "hidden",
+        Image => "image",
+        Month => "month",
+        Number => "number",
+        Password => "password",
+        Radio => "radio",
+        Range => "range",
+        Reset => "reset",
+        Search => "search",
+        Submit => "submit",
+        Tel => "tel",
+        Text => "text",
+        Time => "time",
+        Url => "url",
+        Week => "week",
+    }
+
+    ListType {
+        Decimal => "1",
+        LowerAlpha => "a",
+        UpperAlpha => "A",
+        LowerRoman => "i",
+        UpperRoman => "I",
+    }
+
+    Loading {
+        Eager => "eager",
+        Lazy => "lazy",
+    }
+
+    Popover {
+        Auto => "auto",
+        Manual => "manual",
+    }
+
+    Preload {
+        None => "none",
+        Metadata => "metadata",
+        Auto => "auto",
+    }
+
+    ReferrerPolicy {
+        NoReferrer => "no-referrer",
+        NoReferrerWhenDowngrade => "no-referrer-when-downgrade",
+        Origin => "origin",
+        OriginWhenCrossOrigin => "origin-when-cross-origin",
+        SameOrigin => "same-origin",
+        StrictOrigin => "strict-origin",
+        StrictOriginWhenCrossOrigin => "strict-origin-when-cross-origin",
+        UnsafeUrl => "unsafe-url",
+    }
+
+    Scope {
+        Row => "row",
+        Col => "col",
+        RowGroup => "rowgroup",
+        ColGroup => "colgroup",
+    }
+
+    Shape {
+        Rect => "rect",
+        Circle => "circle",
+        Poly => "poly",
+        Default => "default",
+    }
+
+    TrackKind {
+        Subtitles => "subtitles",
+        Captions => "captions",
+        Descriptions => "descriptions",
+        Chapters => "chapters",
+        Metadata => "metadata",
+    }
+
+    Translate {
+        Yes => "yes",
+        No => "no",
+    }
+
+    Wrap {
+        Soft => "soft",
+        Hard => "hard",
+    }
+}
+
+pub trait Length: Into<Attribute> {}
+
+macro_rules! lengths {
+    ( $( $ty:ty ),* ) => {
+        $( impl Length for $ty {} )*
+    };
+}
+
+lengths!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64, &str, String);
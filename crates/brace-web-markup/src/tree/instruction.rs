@@ -0,0 +1,126 @@
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+use crate::util::escape::{escape_bogus_comment, escape_instruction, is_valid_tag};
+use crate::util::render::{Error, Render, Renderer, Result as RenderResult};
+
+pub fn instruction<T, D>(target: T, data: D) -> Instruction
+where
+    T: Into<String>,
+    D: Into<String>,
+{
+    Instruction::new(target, data)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Instruction {
+    target: String,
+    data: String,
+}
+
+impl Instruction {
+    pub fn new<T, D>(target: T, data: D) -> Self
+    where
+        T: Into<String>,
+        D: Into<String>,
+    {
+        Self {
+            target: target.into(),
+            data: data.into(),
+        }
+    }
+
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    pub fn data(&self) -> &str {
+        &self.data
+    }
+
+    pub fn data_mut(&mut self) -> &mut String {
+        &mut self.data
+    }
+}
+
+impl Render for Instruction {
+    fn render(&self, renderer: &mut Renderer) -> RenderResult {
+        if !is_valid_tag(&self.target) || self.target.eq_ignore_ascii_case("xml") {
+            return Err(Error::InvalidInstruction(self.target.clone()));
+        }
+
+        if self.data.is_empty() {
+            Ok(write!(renderer, "<?{}?>", self.target)?)
+        } else if renderer.is_xml() {
+            Ok(write!(
+                renderer,
+                "<?{} {}?>",
+                self.target,
+                escape_instruction(&self.data)
+            )?)
+        } else {
+            Ok(write!(
+                renderer,
+                "<?{} {}?>",
+                self.target,
+                escape_bogus_comment(&self.data)
+            )?)
+        }
+    }
+
+    fn size_hint(&self) -> usize {
+        self.target.len() + self.data.len() + 5
+    }
+}
+
+impl<T, D> From<(T, D)> for Instruction
+where
+    T: Into<String>,
+    D: Into<String>,
+{
+    fn from(from: (T, D)) -> Self {
+        Self::new(from.0, from.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Instruction;
+    use crate::util::render::{render, render_with, Error, RendererConfig};
+
+    #[test]
+    fn test_instruction_render() {
+        assert_eq!(
+            render(&Instruction::new(
+                "xml-stylesheet",
+                "type=\"text/xsl\" href=\"style.xsl\""
+            ))
+            .unwrap(),
+            "<?xml-stylesheet type=\"text/xsl\" href=\"style.xsl\"?>"
+        );
+        assert_eq!(render(&Instruction::new("php", "")).unwrap(), "<?php?>");
+        assert_eq!(
+            render(&Instruction::new("target", "a ?> b")).unwrap(),
+            "<?target a ?&gt; b?>"
+        );
+        assert_eq!(
+            render(&Instruction::new("target", "x><script>alert(1)</script>")).unwrap(),
+            "<?target x&gt;<script&gt;alert(1)</script&gt;?>"
+        );
+        assert_eq!(
+            render_with(
+                &Instruction::new("target", "a ?> b"),
+                &RendererConfig::xml()
+            )
+            .unwrap(),
+            "<?target a ? > b?>"
+        );
+
+        match render(&Instruction::new("XML", "version=\"1.0\"")) {
+            Err(Error::InvalidInstruction(target)) => assert_eq!(target, "XML"),
+            _ => panic!("expected invalid instruction error"),
+        }
+    }
+}
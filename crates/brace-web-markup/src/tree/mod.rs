@@ -1,4 +1,8 @@
+pub mod comment;
 pub mod document;
 pub mod element;
+pub mod instruction;
 pub mod node;
+pub mod raw;
 pub mod text;
+pub mod visit;
@@ -1,18 +1,29 @@
-use std::collections::vec_deque::{IntoIter, Iter, IterMut, VecDeque};
+use std::collections::vec_deque::{Drain, IntoIter, Iter, IterMut, VecDeque};
+use std::mem;
+use std::ops::{Bound, RangeBounds};
 
 use futures::future::{self, Ready};
 use serde::{Deserialize, Serialize};
 
 use brace_web_core::{HttpRequest, HttpResponse, Responder};
 
-use crate::util::render::{render, Error, Render, Renderer, Result as RenderResult};
-use crate::{Element, Text};
+use crate::tree::visit::{
+    self, Ancestors, BreadthFirst, Descendants, Elements, Paths, Texts, Visit, VisitMut, Visitor,
+    VisitorMut,
+};
+use crate::util::render::{Error, Frame, Render, Renderer, Result as RenderResult};
+use crate::util::selector::{Error as SelectorError, Selector};
+use crate::util::stream::respond;
+use crate::{Comment, Element, Instruction, Raw, Text};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(untagged)]
 pub enum Node {
     Text(Text),
     Element(Element),
+    Raw(Raw),
+    Comment(Comment),
+    Instruction(Instruction),
 }
 
 impl Node {
@@ -71,6 +82,131 @@ impl Node {
             _ => None,
         }
     }
+
+    pub fn raw<T>(raw: T) -> Self
+    where
+        T: Into<Raw>,
+    {
+        Self::Raw(raw.into())
+    }
+
+    pub fn is_raw(&self) -> bool {
+        match self {
+            Self::Raw(_) => true,
+            _ => false,
+        }
+    }
+
+    pub fn as_raw(&self) -> Option<&Raw> {
+        match self {
+            Self::Raw(raw) => Some(raw),
+            _ => None,
+        }
+    }
+
+    pub fn as_raw_mut(&mut self) -> Option<&mut Raw> {
+        match self {
+            Self::Raw(raw) => Some(raw),
+            _ => None,
+        }
+    }
+
+    pub fn comment<T>(comment: T) -> Self
+    where
+        T: Into<Comment>,
+    {
+        Self::Comment(comment.into())
+    }
+
+    pub fn is_comment(&self) -> bool {
+        match self {
+            Self::Comment(_) => true,
+            _ => false,
+        }
+    }
+
+    pub fn as_comment(&self) -> Option<&Comment> {
+        match self {
+            Self::Comment(comment) => Some(comment),
+            _ => None,
+        }
+    }
+
+    pub fn instruction<T>(instruction: T) -> Self
+    where
+        T: Into<Instruction>,
+    {
+        Self::Instruction(instruction.into())
+    }
+
+    pub fn is_instruction(&self) -> bool {
+        match self {
+            Self::Instruction(_) => true,
+            _ => false,
+        }
+    }
+
+    pub fn as_instruction(&self) -> Option<&Instruction> {
+        match self {
+            Self::Instruction(instruction) => Some(instruction),
+            _ => None,
+        }
+    }
+
+    pub fn as_instruction_mut(&mut self) -> Option<&mut Instruction> {
+        match self {
+            Self::Instruction(instruction) => Some(instruction),
+            _ => None,
+        }
+    }
+
+    pub fn as_comment_mut(&mut self) -> Option<&mut Comment> {
+        match self {
+            Self::Comment(comment) => Some(comment),
+            _ => None,
+        }
+    }
+
+    pub fn descendants(&self) -> Descendants<'_> {
+        Descendants::new(self.as_element().map(Element::nodes))
+    }
+
+    pub fn breadth_first(&self) -> BreadthFirst<'_> {
+        BreadthFirst::new(self.as_element().map(Element::nodes))
+    }
+
+    pub fn paths(&self) -> Paths<'_> {
+        Paths::new(self.as_element().map(Element::nodes))
+    }
+
+    pub fn elements(&self) -> Elements<'_> {
+        Elements::new(self.as_element().map(Element::nodes))
+    }
+
+    pub fn texts(&self) -> Texts<'_> {
+        Texts::new(self.as_element().map(Element::nodes))
+    }
+
+    pub fn visit<V>(&self, visitor: &mut V) -> Visit
+    where
+        V: Visitor,
+    {
+        visit::visit_node(self, visitor)
+    }
+
+    pub fn visit_mut<V>(&mut self, visitor: &mut V) -> VisitMut
+    where
+        V: VisitorMut,
+    {
+        visit::visit_node_mut(self, visitor)
+    }
+
+    pub fn is_inline(&self, renderer: &Renderer) -> bool {
+        match self {
+            Self::Element(element) => renderer.is_inline(element.tag()),
+            _ => true,
+        }
+    }
 }
 
 impl Render for Node {
@@ -78,6 +214,19 @@ impl Render for Node {
         match self {
             Self::Text(text) => text.render(renderer),
             Self::Element(element) => element.render(renderer),
+            Self::Raw(raw) => raw.render(renderer),
+            Self::Comment(comment) => comment.render(renderer),
+            Self::Instruction(instruction) => instruction.render(renderer),
+        }
+    }
+
+    fn size_hint(&self) -> usize {
+        match self {
+            Self::Text(text) => text.size_hint(),
+            Self::Element(element) => element.size_hint(),
+            Self::Raw(raw) => raw.size_hint(),
+            Self::Comment(comment) => comment.size_hint(),
+            Self::Instruction(instruction) => instruction.size_hint(),
         }
     }
 }
@@ -86,15 +235,8 @@ impl Responder for Node {
     type Error = Error;
     type Future = Ready<Result<HttpResponse, Self::Error>>;
 
-    fn respond_to(self, _: &HttpRequest) -> Self::Future {
-        match render(&self) {
-            Ok(body) => future::ok(
-                HttpResponse::Ok()
-                    .content_type("text/html; charset=utf-8")
-                    .body(body),
-            ),
-            Err(err) => future::err(err),
-        }
+    fn respond_to(self, req: &HttpRequest) -> Self::Future {
+        future::ready(respond(self, req))
     }
 }
 
@@ -122,6 +264,24 @@ impl From<Element> for Node {
     }
 }
 
+impl From<Raw> for Node {
+    fn from(from: Raw) -> Self {
+        Self::raw(from)
+    }
+}
+
+impl From<Comment> for Node {
+    fn from(from: Comment) -> Self {
+        Self::comment(from)
+    }
+}
+
+impl From<Instruction> for Node {
+    fn from(from: Instruction) -> Self {
+        Self::instruction(from)
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct Nodes(VecDeque<Node>);
 
@@ -142,40 +302,126 @@ impl Nodes {
     where
         T: Into<Node>,
     {
-        let node = node.into();
-
-        if let Node::Text(text) = &node {
-            if let Some(item) = self.0.back_mut().and_then(|item| item.as_text_mut()) {
-                item.value_mut().push_str(&format!(" {}", text.value()));
+        self.0.push_back(node.into());
+        self
+    }
 
-                return self;
-            }
-        }
+    pub fn prepend<T>(&mut self, node: T) -> &mut Self
+    where
+        T: Into<Node>,
+    {
+        self.0.push_front(node.into());
+        self
+    }
 
-        self.0.push_back(node);
+    pub fn insert<T>(&mut self, index: usize, node: T) -> &mut Self
+    where
+        T: Into<Node>,
+    {
+        self.0.insert(index, node.into());
         self
     }
 
-    pub fn prepend<T>(&mut self, node: T) -> &mut Self
+    pub fn remove(&mut self, index: usize) -> Option<Node> {
+        self.0.remove(index)
+    }
+
+    pub fn replace<T>(&mut self, index: usize, node: T) -> Option<Node>
     where
         T: Into<Node>,
     {
-        let mut node = node.into();
+        self.0
+            .get_mut(index)
+            .map(|current| mem::replace(current, node.into()))
+    }
 
-        if let Node::Text(text) = &mut node {
-            if let Some(item) = self.0.front_mut().and_then(|item| item.as_text_mut()) {
-                text.value_mut().push_str(&format!(" {}", item.value()));
+    pub fn swap(&mut self, a: usize, b: usize) -> &mut Self {
+        self.0.swap(a, b);
+        self
+    }
 
-                *item.value_mut() = text.value().to_string();
+    pub fn retain<F>(&mut self, f: F) -> &mut Self
+    where
+        F: FnMut(&Node) -> bool,
+    {
+        self.0.retain(f);
+        self
+    }
 
-                return self;
-            }
+    pub fn splice<R, I>(&mut self, range: R, nodes: I) -> Nodes
+    where
+        R: RangeBounds<usize>,
+        I: IntoIterator<Item = Node>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(start) => *start,
+            Bound::Excluded(start) => *start + 1,
+            Bound::Unbounded => 0,
+        };
+        let removed = self.0.drain(range).collect();
+        let tail = self.0.split_off(start);
+
+        self.0.extend(nodes);
+        self.0.extend(tail);
+
+        Self(removed)
+    }
+
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, Node>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.0.drain(range)
+    }
+
+    pub fn clear(&mut self) -> &mut Self {
+        self.0.clear();
+        self
+    }
+
+    pub fn wrap(&mut self, index: usize, mut wrapper: Element) -> &mut Self {
+        if let Some(node) = self.0.get_mut(index) {
+            let node = mem::replace(node, Node::text(""));
+
+            wrapper.nodes_mut().append(node);
+            self.0[index] = wrapper.into();
+        }
+
+        self
+    }
+
+    pub fn unwrap(&mut self, index: usize) -> &mut Self {
+        if let Some(Node::Element(element)) = self.0.get_mut(index) {
+            let children = element.take_children();
+
+            self.splice(index..=index, children);
         }
 
-        self.0.push_front(node);
         self
     }
 
+    pub fn get_path(&self, path: &[usize]) -> Option<&Node> {
+        let (last, path) = path.split_last()?;
+        let mut nodes = self;
+
+        for index in path {
+            nodes = nodes.get(*index)?.as_element()?.nodes();
+        }
+
+        nodes.get(*last)
+    }
+
+    pub fn get_path_mut(&mut self, path: &[usize]) -> Option<&mut Node> {
+        let (last, path) = path.split_last()?;
+        let mut nodes = self;
+
+        for index in path {
+            nodes = nodes.get_mut(*index)?.as_element_mut()?.nodes_mut();
+        }
+
+        nodes.get_mut(*last)
+    }
+
     pub fn len(&self) -> usize {
         self.0.len()
     }
@@ -191,6 +437,83 @@ impl Nodes {
     pub fn iter_mut(&mut self) -> IterMut<'_, Node> {
         self.0.iter_mut()
     }
+
+    pub fn descendants(&self) -> Descendants<'_> {
+        Descendants::new(Some(self))
+    }
+
+    pub fn breadth_first(&self) -> BreadthFirst<'_> {
+        BreadthFirst::new(Some(self))
+    }
+
+    pub fn paths(&self) -> Paths<'_> {
+        Paths::new(Some(self))
+    }
+
+    pub fn ancestors(&self, path: &[usize]) -> Ancestors<'_> {
+        Ancestors::new(self, path)
+    }
+
+    pub fn elements(&self) -> Elements<'_> {
+        Elements::new(Some(self))
+    }
+
+    pub fn texts(&self) -> Texts<'_> {
+        Texts::new(Some(self))
+    }
+
+    pub fn visit<V>(&self, visitor: &mut V) -> Visit
+    where
+        V: Visitor,
+    {
+        visit::visit(self, visitor)
+    }
+
+    pub fn visit_mut<V>(&mut self, visitor: &mut V) -> VisitMut
+    where
+        V: VisitorMut,
+    {
+        visit::visit_mut(self, visitor)
+    }
+
+    pub fn select(&self, selector: &str) -> Result<Vec<&Element>, SelectorError> {
+        Ok(Selector::parse(selector)?.select(self).collect())
+    }
+
+    pub fn select_first(&self, selector: &str) -> Result<Option<&Element>, SelectorError> {
+        Ok(Selector::parse(selector)?.select(self).next())
+    }
+
+    pub fn select_first_mut(
+        &mut self,
+        selector: &str,
+    ) -> Result<Option<&mut Element>, SelectorError> {
+        let selector = Selector::parse(selector)?;
+        let path = {
+            let mut select = selector.select(self);
+
+            select.next().map(|_| select.path())
+        };
+
+        Ok(path
+            .and_then(move |path| self.get_path_mut(&path))
+            .and_then(Node::as_element_mut))
+    }
+
+    pub fn select_mut<F>(&mut self, selector: &str, mut f: F) -> Result<usize, SelectorError>
+    where
+        F: FnMut(&mut Element),
+    {
+        let paths = Selector::parse(selector)?.select_paths(self);
+
+        for path in &paths {
+            if let Some(Node::Element(element)) = self.get_path_mut(path) {
+                f(element);
+            }
+        }
+
+        Ok(paths.len())
+    }
 }
 
 impl Extend<Node> for Nodes {
@@ -203,18 +526,85 @@ impl IntoIterator for Nodes {
     type Item = Node;
     type IntoIter = IntoIter<Node>;
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.0.into_iter()
+    fn into_iter(mut self) -> Self::IntoIter {
+        mem::take(&mut self.0).into_iter()
+    }
+}
+
+impl Nodes {
+    pub(crate) fn is_collapsible(
+        &self,
+        index: usize,
+        parent: Option<&Element>,
+        renderer: &Renderer,
+    ) -> bool {
+        let is_block = |node: Option<&Node>| match node {
+            Some(Node::Element(element)) => element.is_block(),
+            _ => false,
+        };
+
+        match self.0.get(index) {
+            Some(Node::Text(text)) => {
+                text.value().trim().is_empty()
+                    && !renderer.in_raw_text()
+                    && !parent.map_or(false, |parent| parent.is_preformatted(renderer))
+                    && is_block(index.checked_sub(1).and_then(|index| self.0.get(index)))
+                    && is_block(self.0.get(index + 1))
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Drop for Nodes {
+    fn drop(&mut self) {
+        let is_nested = |node: &Node| match node {
+            Node::Element(element) => !element.nodes().is_empty(),
+            _ => false,
+        };
+
+        if !self.0.iter().any(is_nested) {
+            return;
+        }
+
+        let mut stack = vec![mem::take(&mut self.0)];
+
+        while let Some(mut nodes) = stack.pop() {
+            for node in nodes.iter_mut() {
+                if let Node::Element(element) = node {
+                    if !element.nodes().is_empty() {
+                        stack.push(mem::take(&mut element.nodes_mut().0));
+                    }
+                }
+            }
+        }
     }
 }
 
 impl Render for Nodes {
     fn render(&self, renderer: &mut Renderer) -> RenderResult {
-        for node in &self.0 {
-            node.render(renderer)?;
+        let frame = Frame::root(renderer, false);
+
+        renderer.walk(self, None, frame)
+    }
+
+    fn size_hint(&self) -> usize {
+        let mut size = 0;
+        let mut stack = vec![self];
+
+        while let Some(nodes) = stack.pop() {
+            for node in nodes {
+                match node {
+                    Node::Element(element) => {
+                        size += element.markup_size_hint();
+                        stack.push(element.nodes());
+                    }
+                    node => size += node.size_hint(),
+                }
+            }
         }
 
-        Ok(())
+        size
     }
 }
 
@@ -222,15 +612,8 @@ impl Responder for Nodes {
     type Error = Error;
     type Future = Ready<Result<HttpResponse, Self::Error>>;
 
-    fn respond_to(self, _: &HttpRequest) -> Self::Future {
-        match render(&self) {
-            Ok(body) => future::ok(
-                HttpResponse::Ok()
-                    .content_type("text/html; charset=utf-8")
-                    .body(body),
-            ),
-            Err(err) => future::err(err),
-        }
+    fn respond_to(self, req: &HttpRequest) -> Self::Future {
+        future::ready(respond(self, req))
     }
 }
 
@@ -300,6 +683,24 @@ impl From<Vec<Element>> for Nodes {
     }
 }
 
+impl From<Raw> for Nodes {
+    fn from(from: Raw) -> Self {
+        Self(vec![Node::from(from)].into())
+    }
+}
+
+impl From<Comment> for Nodes {
+    fn from(from: Comment) -> Self {
+        Self(vec![Node::from(from)].into())
+    }
+}
+
+impl From<Instruction> for Nodes {
+    fn from(from: Instruction) -> Self {
+        Self(vec![Node::from(from)].into())
+    }
+}
+
 impl From<Node> for Nodes {
     fn from(from: Node) -> Self {
         Self(vec![from].into())
@@ -333,7 +734,8 @@ impl From<Vec<Node>> for Nodes {
 #[cfg(test)]
 mod tests {
     use super::Nodes;
-    use crate::{Element, Node, Text};
+    use crate::util::render::render;
+    use crate::{Comment, Element, Node, Raw, Text};
 
     #[test]
     fn test_node_impl() {
@@ -360,6 +762,32 @@ mod tests {
         assert!(node_4.is_element());
         assert!(node_4.as_element().is_some());
         assert!(node_4.as_element_mut().is_some());
+
+        let mut node_5 = Node::Raw(Raw::new("<b>hello world</b>"));
+
+        assert!(node_5.is_raw());
+        assert!(node_5.as_raw().is_some());
+        assert!(node_5.as_raw_mut().is_some());
+
+        let mut node_6 = Node::raw("<b>hello world</b>");
+
+        assert!(node_6.is_raw());
+        assert!(!node_6.is_text());
+        assert!(node_6.as_raw().is_some());
+        assert!(node_6.as_raw_mut().is_some());
+
+        let mut node_7 = Node::Comment(Comment::new("hello world"));
+
+        assert!(node_7.is_comment());
+        assert!(node_7.as_comment().is_some());
+        assert!(node_7.as_comment_mut().is_some());
+
+        let mut node_8 = Node::comment("hello world");
+
+        assert!(node_8.is_comment());
+        assert!(!node_8.is_text());
+        assert!(node_8.as_comment().is_some());
+        assert!(node_8.as_comment_mut().is_some());
     }
 
     #[test]
@@ -449,10 +877,14 @@ mod tests {
         element_1.nodes_mut().append(Text::new("one"));
         element_1.nodes_mut().append(Text::new("two"));
 
-        assert_eq!(element_1.nodes().len(), 1);
+        assert_eq!(element_1.nodes().len(), 2);
         assert_eq!(
             element_1.nodes().get(0).unwrap().as_text().unwrap().value(),
-            "one two"
+            "one"
+        );
+        assert_eq!(
+            element_1.nodes().get(1).unwrap().as_text().unwrap().value(),
+            "two"
         );
 
         let mut element_2 = Element::new("span");
@@ -460,10 +892,59 @@ mod tests {
         element_2.nodes_mut().append(Text::new("one"));
         element_2.nodes_mut().prepend(Text::new("two"));
 
-        assert_eq!(element_2.nodes().len(), 1);
+        assert_eq!(element_2.nodes().len(), 2);
         assert_eq!(
             element_2.nodes().get(0).unwrap().as_text().unwrap().value(),
-            "two one"
+            "two"
+        );
+        assert_eq!(
+            element_2.nodes().get(1).unwrap().as_text().unwrap().value(),
+            "one"
         );
+
+        let element_3 = Element::new("p")
+            .with_node(Text::new("Hello"))
+            .with_node(Text::new(","))
+            .with_node(Text::new("world"));
+
+        assert_eq!(render(&element_3).unwrap(), "<p>Hello,world</p>");
+    }
+
+    #[test]
+    fn test_node_manipulation() {
+        let mut nodes = Nodes::from(vec![
+            Element::new("a"),
+            Element::new("b"),
+            Element::new("c"),
+        ]);
+
+        nodes.insert(1, "x").swap(0, 2);
+
+        assert_eq!(render(&nodes).unwrap(), "<b></b>x<a></a><c></c>");
+        assert_eq!(nodes.replace(1, "y"), Some(Node::text("x")));
+        assert_eq!(nodes.replace(9, "z"), None);
+        assert_eq!(nodes.remove(0), Some(Node::element("b")));
+
+        nodes.retain(Node::is_element);
+
+        assert_eq!(render(&nodes).unwrap(), "<a></a><c></c>");
+
+        let removed = nodes.splice(
+            1..,
+            vec![Node::text("one"), Node::text("two"), Node::element("i")],
+        );
+
+        assert_eq!(render(&removed).unwrap(), "<c></c>");
+        assert_eq!(render(&nodes).unwrap(), "<a></a>onetwo<i></i>");
+        assert_eq!(nodes.drain(1..3).count(), 2);
+
+        nodes.wrap(0, Element::new("p")).wrap(1, Element::new("em"));
+
+        assert_eq!(render(&nodes).unwrap(), "<p><a></a></p><em><i></i></em>");
+
+        nodes.unwrap(0).unwrap(1);
+
+        assert_eq!(render(&nodes).unwrap(), "<a></a><i></i>");
+        assert!(nodes.clear().is_empty());
     }
 }
@@ -0,0 +1,80 @@
+use std::fmt::{self, Display, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::util::render::{Render, Renderer, Result as RenderResult};
+
+pub fn raw<T>(raw: T) -> Raw
+where
+    T: Into<String>,
+{
+    Raw::new(raw)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Raw {
+    raw: String,
+}
+
+impl Raw {
+    pub fn new<T>(raw: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self { raw: raw.into() }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.raw
+    }
+
+    pub fn value_mut(&mut self) -> &mut String {
+        &mut self.raw
+    }
+}
+
+impl Render for Raw {
+    fn render(&self, renderer: &mut Renderer) -> RenderResult {
+        Ok(write!(renderer, "{}", self.raw)?)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.raw.len()
+    }
+}
+
+impl Display for Raw {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&self.raw, f)
+    }
+}
+
+impl From<&str> for Raw {
+    fn from(from: &str) -> Self {
+        Self::new(from)
+    }
+}
+
+impl From<String> for Raw {
+    fn from(from: String) -> Self {
+        Self::new(from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Raw;
+    use crate::util::render::render;
+
+    #[test]
+    fn test_raw_verbatim() {
+        let raw = Raw::new("<p>\n  <b>Hello</b> &amp; world\n</p>");
+
+        assert_eq!(raw.value(), "<p>\n  <b>Hello</b> &amp; world\n</p>");
+        assert_eq!(
+            render(&raw).unwrap(),
+            "<p>\n  <b>Hello</b> &amp; world\n</p>"
+        );
+    }
+}
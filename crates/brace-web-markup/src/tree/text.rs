@@ -4,6 +4,7 @@ use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 
+use crate::util::escape::{escape_raw_text, escape_text};
 use crate::util::render::{Render, Renderer, Result as RenderResult};
 
 static REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s+").unwrap());
@@ -27,6 +28,13 @@ impl Text {
         Self(REGEX.replace_all(text.as_ref(), " ").trim().to_string())
     }
 
+    pub fn preserved<T>(text: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self(text.into())
+    }
+
     pub fn value(&self) -> &str {
         &self.0
     }
@@ -38,7 +46,15 @@ impl Text {
 
 impl Render for Text {
     fn render(&self, renderer: &mut Renderer) -> RenderResult {
-        Ok(write!(renderer, "{}", self.0)?)
+        if renderer.in_raw_text() {
+            Ok(write!(renderer, "{}", escape_raw_text(&self.0))?)
+        } else {
+            Ok(write!(renderer, "{}", escape_text(&self.0))?)
+        }
+    }
+
+    fn size_hint(&self) -> usize {
+        self.0.len()
     }
 }
 
@@ -63,6 +79,8 @@ impl From<String> for Text {
 #[cfg(test)]
 mod tests {
     use super::Text;
+    use crate::util::render::render;
+    use crate::Element;
 
     #[test]
     fn test_whitespace() {
@@ -76,4 +94,33 @@ mod tests {
         assert_eq!(c.0, "hello world");
         assert_eq!(d.0, "hello world");
     }
+
+    #[test]
+    fn test_whitespace_preserved() {
+        let a = Text::preserved("  hello\n    world  ");
+
+        assert_eq!(a.0, "  hello\n    world  ");
+        assert_eq!(
+            render(&Element::new("pre").with_node(a)).unwrap(),
+            "<pre>  hello\n    world  </pre>"
+        );
+    }
+
+    #[test]
+    fn test_raw_text() {
+        let script = Element::new("script")
+            .with_node(Text::preserved("if (a < b && b > c) { x = '</script>'; }"));
+
+        assert_eq!(
+            render(&script).unwrap(),
+            "<script>if (a < b && b > c) { x = '<\\/script>'; }</script>"
+        );
+
+        let textarea = Element::new("textarea").with_node(Text::preserved("a < b\n"));
+
+        assert_eq!(
+            render(&textarea).unwrap(),
+            "<textarea>a &lt; b\n</textarea>"
+        );
+    }
 }
@@ -0,0 +1,513 @@
+use std::collections::vec_deque::{Iter, VecDeque};
+use std::mem;
+
+use crate::{Element, Node, Nodes, Text};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Visit {
+    Continue,
+    Skip,
+    Stop,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum VisitMut {
+    Continue,
+    Skip,
+    Stop,
+    Remove,
+    Replace(Node),
+}
+
+pub trait Visitor {
+    fn enter(&mut self, _: &Node) -> Visit {
+        Visit::Continue
+    }
+
+    fn leave(&mut self, _: &Node) -> Visit {
+        Visit::Continue
+    }
+}
+
+pub trait VisitorMut {
+    fn enter(&mut self, _: &mut Node) -> VisitMut {
+        VisitMut::Continue
+    }
+
+    fn leave(&mut self, _: &mut Node) -> VisitMut {
+        VisitMut::Continue
+    }
+}
+
+pub struct Descendants<'a> {
+    stack: Vec<Iter<'a, Node>>,
+}
+
+impl<'a> Descendants<'a> {
+    pub(crate) fn new(nodes: Option<&'a Nodes>) -> Self {
+        Self {
+            stack: nodes.map(Nodes::iter).into_iter().collect(),
+        }
+    }
+}
+
+impl<'a> Iterator for Descendants<'a> {
+    type Item = &'a Node;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.stack.last_mut()?.next() {
+                Some(node) => {
+                    if let Node::Element(element) = node {
+                        self.stack.push(element.nodes().iter());
+                    }
+
+                    return Some(node);
+                }
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+    }
+}
+
+pub struct BreadthFirst<'a> {
+    queue: VecDeque<&'a Node>,
+}
+
+impl<'a> BreadthFirst<'a> {
+    pub(crate) fn new(nodes: Option<&'a Nodes>) -> Self {
+        Self {
+            queue: nodes.into_iter().flatten().collect(),
+        }
+    }
+}
+
+impl<'a> Iterator for BreadthFirst<'a> {
+    type Item = &'a Node;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.queue.pop_front()?;
+
+        if let Node::Element(element) = node {
+            self.queue.extend(element.nodes());
+        }
+
+        Some(node)
+    }
+}
+
+pub struct Paths<'a> {
+    stack: Vec<(Iter<'a, Node>, usize)>,
+}
+
+impl<'a> Paths<'a> {
+    pub(crate) fn new(nodes: Option<&'a Nodes>) -> Self {
+        Self {
+            stack: nodes.map(|nodes| (nodes.iter(), 0)).into_iter().collect(),
+        }
+    }
+}
+
+impl<'a> Iterator for Paths<'a> {
+    type Item = (Vec<usize>, &'a Node);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (iter, index) = self.stack.last_mut()?;
+
+            match iter.next() {
+                Some(node) => {
+                    *index += 1;
+
+                    let path = self.stack.iter().map(|(_, index)| index - 1).collect();
+
+                    if let Node::Element(element) = node {
+                        self.stack.push((element.nodes().iter(), 0));
+                    }
+
+                    return Some((path, node));
+                }
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+    }
+}
+
+pub struct Ancestors<'a> {
+    elements: Vec<&'a Element>,
+}
+
+impl<'a> Ancestors<'a> {
+    pub(crate) fn new(nodes: &'a Nodes, path: &[usize]) -> Self {
+        let mut nodes = nodes;
+        let mut elements = Vec::new();
+
+        for index in path.iter().take(path.len().saturating_sub(1)) {
+            match nodes.get(*index).and_then(Node::as_element) {
+                Some(element) => {
+                    nodes = element.nodes();
+                    elements.push(element);
+                }
+                None => break,
+            }
+        }
+
+        Self { elements }
+    }
+}
+
+impl<'a> Iterator for Ancestors<'a> {
+    type Item = &'a Element;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.elements.pop()
+    }
+}
+
+pub struct Elements<'a>(Descendants<'a>);
+
+impl<'a> Elements<'a> {
+    pub(crate) fn new(nodes: Option<&'a Nodes>) -> Self {
+        Self(Descendants::new(nodes))
+    }
+}
+
+impl<'a> Iterator for Elements<'a> {
+    type Item = &'a Element;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.find_map(Node::as_element)
+    }
+}
+
+pub struct Texts<'a>(Descendants<'a>);
+
+impl<'a> Texts<'a> {
+    pub(crate) fn new(nodes: Option<&'a Nodes>) -> Self {
+        Self(Descendants::new(nodes))
+    }
+}
+
+impl<'a> Iterator for Texts<'a> {
+    type Item = &'a Text;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.find_map(Node::as_text)
+    }
+}
+
+pub(crate) fn visit_node<V>(node: &Node, visitor: &mut V) -> Visit
+where
+    V: Visitor + ?Sized,
+{
+    match visitor.enter(node) {
+        Visit::Stop => return Visit::Stop,
+        Visit::Continue => {
+            if let Node::Element(element) = node {
+                if visit(element.nodes(), visitor) == Visit::Stop {
+                    return Visit::Stop;
+                }
+            }
+        }
+        Visit::Skip => (),
+    }
+
+    visitor.leave(node)
+}
+
+pub(crate) fn visit<V>(nodes: &Nodes, visitor: &mut V) -> Visit
+where
+    V: Visitor + ?Sized,
+{
+    let mut stack = vec![(nodes.iter(), None)];
+
+    while let Some((iter, _)) = stack.last_mut() {
+        let node = match iter.next() {
+            Some(node) => node,
+            None => {
+                if let Some((_, Some(parent))) = stack.pop() {
+                    if visitor.leave(parent) == Visit::Stop {
+                        return Visit::Stop;
+                    }
+                }
+
+                continue;
+            }
+        };
+
+        match (visitor.enter(node), node) {
+            (Visit::Stop, _) => return Visit::Stop,
+            (Visit::Continue, Node::Element(element)) => {
+                stack.push((element.nodes().iter(), Some(node)));
+                continue;
+            }
+            _ => (),
+        }
+
+        if visitor.leave(node) == Visit::Stop {
+            return Visit::Stop;
+        }
+    }
+
+    Visit::Continue
+}
+
+pub(crate) fn visit_node_mut<V>(node: &mut Node, visitor: &mut V) -> VisitMut
+where
+    V: VisitorMut + ?Sized,
+{
+    let action = match visitor.enter(node) {
+        VisitMut::Continue => match node {
+            Node::Element(element) => match visit_mut(element.nodes_mut(), visitor) {
+                VisitMut::Stop => return VisitMut::Stop,
+                _ => visitor.leave(node),
+            },
+            _ => visitor.leave(node),
+        },
+        VisitMut::Skip => visitor.leave(node),
+        action => action,
+    };
+
+    // The root node has no parent to be removed from, so only replacement applies.
+    if let VisitMut::Replace(replacement) = action {
+        *node = replacement;
+
+        return VisitMut::Continue;
+    }
+
+    action
+}
+
+pub(crate) fn visit_mut<V>(nodes: &mut Nodes, visitor: &mut V) -> VisitMut
+where
+    V: VisitorMut + ?Sized,
+{
+    let mut stack = vec![(mem::take(nodes), 0)];
+    let mut stopped = false;
+
+    while let Some((current, index)) = stack.last_mut() {
+        if stopped || *index >= current.len() {
+            let children = match stack.pop() {
+                Some((children, _)) => children,
+                None => break,
+            };
+            let (parent, index) = match stack.last_mut() {
+                Some(frame) => frame,
+                None => {
+                    *nodes = children;
+                    break;
+                }
+            };
+            let node = match parent.get_mut(*index) {
+                Some(node) => node,
+                None => continue,
+            };
+
+            if let Node::Element(element) = node {
+                *element.nodes_mut() = children;
+            }
+
+            if !stopped {
+                let action = visitor.leave(node);
+
+                stopped = apply(parent, index, action);
+            }
+
+            continue;
+        }
+
+        let node = match current.get_mut(*index) {
+            Some(node) => node,
+            None => continue,
+        };
+        let action = match visitor.enter(node) {
+            VisitMut::Continue => match node {
+                Node::Element(element) => {
+                    let children = mem::take(element.nodes_mut());
+
+                    stack.push((children, 0));
+                    continue;
+                }
+                _ => visitor.leave(node),
+            },
+            VisitMut::Skip => visitor.leave(node),
+            action => action,
+        };
+
+        stopped = apply(current, index, action);
+    }
+
+    if stopped {
+        VisitMut::Stop
+    } else {
+        VisitMut::Continue
+    }
+}
+
+fn apply(nodes: &mut Nodes, index: &mut usize, action: VisitMut) -> bool {
+    match action {
+        VisitMut::Continue | VisitMut::Skip => *index += 1,
+        VisitMut::Stop => return true,
+        VisitMut::Remove => {
+            nodes.remove(*index);
+        }
+        VisitMut::Replace(node) => {
+            if let Some(current) = nodes.get_mut(*index) {
+                *current = node;
+            }
+
+            *index += 1;
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Visit, VisitMut, Visitor, VisitorMut};
+    use crate::util::render::render;
+    use crate::{Document, Element, Node, Text};
+
+    fn document() -> Document {
+        Document::new().with_node(
+            Element::new("html").with_node(
+                Element::new("body")
+                    .with_node(
+                        Element::new("h1")
+                            .with_attr("id", "title")
+                            .with_node("Title"),
+                    )
+                    .with_node(
+                        Element::new("ul")
+                            .with_node(Element::new("li").with_node("One"))
+                            .with_node(Element::new("li").with_node("Two")),
+                    )
+                    .with_node(Node::comment("note")),
+            ),
+        )
+    }
+
+    #[test]
+    fn test_visit_iterators() {
+        let document = document();
+        let tags = |iter: &mut dyn Iterator<Item = &Node>| {
+            iter.filter_map(Node::as_element)
+                .map(Element::tag)
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+
+        assert_eq!(tags(&mut document.descendants()), "html body h1 ul li li");
+        assert_eq!(tags(&mut document.breadth_first()), "html body h1 ul li li");
+        assert_eq!(
+            document
+                .breadth_first()
+                .filter_map(|node| node.as_text().map(Text::value))
+                .collect::<Vec<_>>(),
+            vec!["Title", "One", "Two"]
+        );
+        assert_eq!(
+            document.texts().map(Text::value).collect::<Vec<_>>(),
+            vec!["Title", "One", "Two"]
+        );
+        assert_eq!(document.elements().count(), 6);
+        assert_eq!(document.get_node(0).unwrap().descendants().count(), 9);
+
+        let (path, _) = document
+            .paths()
+            .find(|(_, node)| node.as_text().map(Text::value) == Some("Two"))
+            .unwrap();
+
+        assert_eq!(path, vec![0, 0, 1, 1, 0]);
+        assert_eq!(
+            document
+                .ancestors(&path)
+                .map(Element::tag)
+                .collect::<Vec<_>>(),
+            vec!["li", "ul", "body", "html"]
+        );
+        assert_eq!(
+            document.get_path(&path).and_then(Node::as_text),
+            Some(&Text::new("Two"))
+        );
+    }
+
+    #[test]
+    fn test_visit_visitor() {
+        struct Outline(Vec<String>);
+
+        impl Visitor for Outline {
+            fn enter(&mut self, node: &Node) -> Visit {
+                match node {
+                    Node::Element(element) if element.tag() == "ul" => Visit::Skip,
+                    Node::Element(element) => {
+                        self.0.push(format!("<{}>", element.tag()));
+                        Visit::Continue
+                    }
+                    Node::Comment(_) => Visit::Stop,
+                    _ => Visit::Continue,
+                }
+            }
+
+            fn leave(&mut self, node: &Node) -> Visit {
+                if let Node::Element(element) = node {
+                    self.0.push(format!("</{}>", element.tag()));
+                }
+
+                Visit::Continue
+            }
+        }
+
+        let mut outline = Outline(Vec::new());
+
+        assert_eq!(document().visit(&mut outline), Visit::Stop);
+        assert_eq!(outline.0.join(""), "<html><body><h1></h1></ul>");
+    }
+
+    #[test]
+    fn test_visit_visitor_mut() {
+        struct Transform;
+
+        impl VisitorMut for Transform {
+            fn enter(&mut self, node: &mut Node) -> VisitMut {
+                match node {
+                    Node::Comment(_) => VisitMut::Remove,
+                    Node::Element(element) if element.tag() == "h1" => {
+                        VisitMut::Replace(Element::new("h2").with_node("Heading").into())
+                    }
+                    _ => VisitMut::Continue,
+                }
+            }
+
+            fn leave(&mut self, node: &mut Node) -> VisitMut {
+                match node {
+                    Node::Element(element) if element.tag() == "li" => {
+                        element.set_attr("class", "item");
+                        VisitMut::Continue
+                    }
+                    Node::Text(text) if text.value() == "Two" => VisitMut::Remove,
+                    _ => VisitMut::Continue,
+                }
+            }
+        }
+
+        let mut document = document();
+
+        assert_eq!(document.visit_mut(&mut Transform), VisitMut::Continue);
+        assert_eq!(
+            render(&document).unwrap(),
+            "<!DOCTYPE html><html><body><h2>Heading</h2><ul>\
+             <li class=\"item\">One</li><li class=\"item\"></li></ul></body></html>"
+        );
+
+        let mut node = Node::from(Element::new("h1"));
+
+        assert_eq!(node.visit_mut(&mut Transform), VisitMut::Continue);
+        assert_eq!(render(&node).unwrap(), "<h2>Heading</h2>");
+    }
+}
@@ -0,0 +1,395 @@
+use std::fmt::{Result as FmtResult, Write};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use brace_web_core::dev::HttpResponseBuilder;
+use brace_web_core::http::header::{
+    CacheControl, CacheDirective, HttpDate, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED,
+};
+use brace_web_core::http::Method;
+use brace_web_core::{HttpRequest, HttpResponse, Responder};
+use futures::future::{self, Ready};
+
+use crate::util::context::RenderContext;
+use crate::util::render::{Error, Render, Renderer};
+use crate::util::stream::{respond_with, RenderStream};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EntityTag {
+    Strong,
+    Weak,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CachePolicy {
+    etag: Option<EntityTag>,
+    streamed: bool,
+    last_modified: Option<SystemTime>,
+    cache_control: Vec<CacheDirective>,
+}
+
+impl CachePolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn strong(mut self) -> Self {
+        self.etag = Some(EntityTag::Strong);
+        self.streamed = true;
+        self
+    }
+
+    pub fn weak(mut self) -> Self {
+        self.etag = Some(EntityTag::Weak);
+        self.streamed = true;
+        self
+    }
+
+    pub fn no_etag(mut self) -> Self {
+        self.etag = None;
+        self.streamed = false;
+        self
+    }
+
+    pub fn last_modified(mut self, last_modified: SystemTime) -> Self {
+        self.last_modified = Some(last_modified);
+        self
+    }
+
+    pub fn cache_control(mut self, directive: CacheDirective) -> Self {
+        self.cache_control.push(directive);
+        self
+    }
+
+    pub fn etag(&self) -> Option<EntityTag> {
+        self.etag
+    }
+
+    pub(crate) fn etag_of(&self, body: &[u8]) -> Option<String> {
+        let kind = self.etag?;
+        let mut hasher = Hasher::new();
+
+        hasher.update(body);
+
+        Some(hasher.etag(kind))
+    }
+
+    pub(crate) fn etag_for<T>(
+        &self,
+        item: &T,
+        context: &RenderContext,
+    ) -> Result<Option<String>, Error>
+    where
+        T: Render,
+    {
+        let kind = match self.etag {
+            Some(kind) if self.streamed => kind,
+            _ => return Ok(None),
+        };
+        let mut hasher = Hasher::new();
+
+        Renderer::new(&mut hasher)
+            .with_context(context)
+            .render(item)?;
+
+        Ok(Some(hasher.etag(kind)))
+    }
+
+    pub(crate) fn is_fresh(&self, req: &HttpRequest, etag: Option<&str>) -> bool {
+        if req.method() != Method::GET && req.method() != Method::HEAD {
+            return false;
+        }
+
+        if let Some(header) = req.headers().get(IF_NONE_MATCH) {
+            let (etag, header) = match (etag, header.to_str()) {
+                (Some(etag), Ok(header)) => (etag, header),
+                _ => return false,
+            };
+
+            return header
+                .split(',')
+                .map(str::trim)
+                .any(|tag| tag == "*" || opaque(tag) == opaque(etag));
+        }
+
+        let since = req
+            .headers()
+            .get(IF_MODIFIED_SINCE)
+            .and_then(|header| header.to_str().ok())
+            .and_then(|header| header.parse::<HttpDate>().ok())
+            .map(SystemTime::from);
+
+        match (since, self.last_modified) {
+            (Some(since), Some(last_modified)) => truncate(last_modified) <= since,
+            _ => false,
+        }
+    }
+
+    pub(crate) fn apply(&self, builder: &mut HttpResponseBuilder, etag: Option<&str>) {
+        if let Some(etag) = etag {
+            builder.header(ETAG, etag);
+        }
+
+        if let Some(last_modified) = self.last_modified {
+            builder.header(LAST_MODIFIED, HttpDate::from(last_modified).to_string());
+        }
+
+        if !self.cache_control.is_empty() {
+            builder.set(CacheControl(self.cache_control.clone()));
+        }
+    }
+}
+
+impl Default for CachePolicy {
+    fn default() -> Self {
+        Self {
+            etag: Some(EntityTag::Strong),
+            streamed: false,
+            last_modified: None,
+            cache_control: Vec::new(),
+        }
+    }
+}
+
+pub struct Cached<T> {
+    item: T,
+    policy: CachePolicy,
+}
+
+impl<T> Cached<T> {
+    pub fn new(item: T) -> Self {
+        Self {
+            item,
+            policy: CachePolicy::default(),
+        }
+    }
+
+    pub fn with_policy(mut self, policy: CachePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn strong(self) -> Self {
+        self.map(CachePolicy::strong)
+    }
+
+    pub fn weak(self) -> Self {
+        self.map(CachePolicy::weak)
+    }
+
+    pub fn no_etag(self) -> Self {
+        self.map(CachePolicy::no_etag)
+    }
+
+    pub fn last_modified(self, last_modified: SystemTime) -> Self {
+        self.map(|policy| policy.last_modified(last_modified))
+    }
+
+    pub fn cache_control(self, directive: CacheDirective) -> Self {
+        self.map(|policy| policy.cache_control(directive))
+    }
+
+    fn map<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(CachePolicy) -> CachePolicy,
+    {
+        self.policy = f(self.policy);
+        self
+    }
+}
+
+impl<T> Responder for Cached<T>
+where
+    T: Render + Into<RenderStream>,
+{
+    type Error = Error;
+    type Future = Ready<Result<HttpResponse, Self::Error>>;
+
+    fn respond_to(self, req: &HttpRequest) -> Self::Future {
+        future::ready(respond_with(self.item, req, &self.policy))
+    }
+}
+
+struct Hasher(u64);
+
+impl Hasher {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn etag(&self, kind: EntityTag) -> String {
+        match kind {
+            EntityTag::Strong => format!("\"{:016x}\"", self.0),
+            EntityTag::Weak => format!("W/\"{:016x}\"", self.0),
+        }
+    }
+}
+
+impl Write for Hasher {
+    fn write_str(&mut self, s: &str) -> FmtResult {
+        self.update(s.as_bytes());
+
+        Ok(())
+    }
+}
+
+fn opaque(tag: &str) -> &str {
+    tag.trim_start_matches("W/")
+}
+
+fn truncate(time: SystemTime) -> SystemTime {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => UNIX_EPOCH + Duration::from_secs(duration.as_secs()),
+        Err(_) => time,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use brace_web_core::http::header::{
+        CacheDirective, HttpDate, CACHE_CONTROL, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH,
+        LAST_MODIFIED,
+    };
+    use brace_web_core::http::{Method, StatusCode};
+    use brace_web_core::test::TestRequest;
+    use brace_web_core::Responder;
+
+    use super::Cached;
+    use crate::Element;
+
+    fn element() -> Element {
+        Element::new("p").with_node("Hello world")
+    }
+
+    #[actix_rt::test]
+    async fn test_cache_etag() {
+        let req = TestRequest::default().to_http_request();
+        let res = element().respond_to(&req).await.unwrap();
+        let etag = res
+            .headers()
+            .get(ETAG)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_owned();
+
+        assert_eq!(res.status(), StatusCode::OK);
+        assert!(etag.starts_with('"'));
+
+        let req = TestRequest::default()
+            .header(IF_NONE_MATCH, format!("\"other\", {}", etag))
+            .to_http_request();
+        let res = element().respond_to(&req).await.unwrap();
+
+        assert_eq!(res.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(res.headers().get(ETAG).unwrap().to_str().unwrap(), etag);
+
+        let req = TestRequest::default()
+            .header(IF_NONE_MATCH, "\"other\"")
+            .to_http_request();
+        let res = Element::new("p").respond_to(&req).await.unwrap();
+
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let req = TestRequest::default()
+            .method(Method::POST)
+            .header(IF_NONE_MATCH, etag.as_str())
+            .to_http_request();
+        let res = element().respond_to(&req).await.unwrap();
+
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let req = TestRequest::default()
+            .header(IF_NONE_MATCH, format!("W/{}", etag))
+            .to_http_request();
+        let res = Cached::new(element())
+            .weak()
+            .respond_to(&req)
+            .await
+            .unwrap();
+
+        assert_eq!(res.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(
+            res.headers().get(ETAG).unwrap().to_str().unwrap(),
+            format!("W/{}", etag)
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_cache_streamed() {
+        let element = || Element::new("p").with_node("a".repeat(128 * 1024));
+        let req = TestRequest::default().to_http_request();
+        let res = element().respond_to(&req).await.unwrap();
+
+        assert_eq!(res.status(), StatusCode::OK);
+        assert!(res.headers().get(ETAG).is_none());
+
+        let res = Cached::new(element())
+            .strong()
+            .respond_to(&req)
+            .await
+            .unwrap();
+        let etag = res.headers().get(ETAG).unwrap().clone();
+
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let req = TestRequest::default()
+            .header(IF_NONE_MATCH, etag)
+            .to_http_request();
+        let res = Cached::new(element())
+            .strong()
+            .respond_to(&req)
+            .await
+            .unwrap();
+
+        assert_eq!(res.status(), StatusCode::NOT_MODIFIED);
+    }
+
+    #[actix_rt::test]
+    async fn test_cache_last_modified() {
+        let modified = UNIX_EPOCH + Duration::from_secs(1_500_000_000);
+        let since = HttpDate::from(modified + Duration::from_secs(60)).to_string();
+
+        let req = TestRequest::default()
+            .header(IF_MODIFIED_SINCE, since.as_str())
+            .to_http_request();
+        let res = Cached::new(element())
+            .no_etag()
+            .last_modified(modified + Duration::from_millis(500))
+            .cache_control(CacheDirective::Public)
+            .cache_control(CacheDirective::MaxAge(3600))
+            .respond_to(&req)
+            .await
+            .unwrap();
+
+        assert_eq!(res.status(), StatusCode::NOT_MODIFIED);
+        assert!(res.headers().get(ETAG).is_none());
+        assert_eq!(
+            res.headers().get(LAST_MODIFIED).unwrap().to_str().unwrap(),
+            HttpDate::from(modified).to_string()
+        );
+        assert_eq!(
+            res.headers().get(CACHE_CONTROL).unwrap().to_str().unwrap(),
+            "public, max-age=3600"
+        );
+
+        let req = TestRequest::default()
+            .header(IF_MODIFIED_SINCE, HttpDate::from(UNIX_EPOCH).to_string())
+            .to_http_request();
+        let res = Cached::new(element())
+            .last_modified(modified)
+            .respond_to(&req)
+            .await
+            .unwrap();
+
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,216 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::sync::Arc;
+
+use brace_web_core::http::header::ACCEPT_LANGUAGE;
+use brace_web_core::HttpRequest;
+use once_cell::sync::Lazy;
+
+pub(crate) static CONTEXT: Lazy<RenderContext> = Lazy::new(RenderContext::default);
+
+#[derive(Clone, Default)]
+pub struct RenderContext {
+    path: Option<String>,
+    locale: Option<String>,
+    nonce: Option<String>,
+    csrf_token: Option<String>,
+    data: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+}
+
+impl RenderContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_request(req: &HttpRequest) -> Self {
+        let mut context = req
+            .extensions()
+            .get::<RenderContext>()
+            .cloned()
+            .unwrap_or_default();
+
+        if context.path.is_none() {
+            context.path = Some(req.path().to_owned());
+        }
+
+        if context.locale.is_none() {
+            context.locale = req
+                .headers()
+                .get(ACCEPT_LANGUAGE)
+                .and_then(|value| value.to_str().ok())
+                .and_then(parse_locale);
+        }
+
+        context
+    }
+
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    pub fn with_path<T>(mut self, path: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.path = Some(path.into());
+        self
+    }
+
+    pub fn locale(&self) -> Option<&str> {
+        self.locale.as_deref()
+    }
+
+    pub fn with_locale<T>(mut self, locale: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    pub fn nonce(&self) -> Option<&str> {
+        self.nonce.as_deref()
+    }
+
+    pub fn with_nonce<T>(mut self, nonce: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.nonce = Some(nonce.into());
+        self
+    }
+
+    pub fn csrf_token(&self) -> Option<&str> {
+        self.csrf_token.as_deref()
+    }
+
+    pub fn with_csrf_token<T>(mut self, csrf_token: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.csrf_token = Some(csrf_token.into());
+        self
+    }
+
+    pub fn user<T>(&self) -> Option<&T>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.get::<User<T>>().map(|user| &user.0)
+    }
+
+    pub fn with_user<T>(self, user: T) -> Self
+    where
+        T: Send + Sync + 'static,
+    {
+        self.with(User(user))
+    }
+
+    pub fn get<T>(&self) -> Option<&T>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.data
+            .get(&TypeId::of::<T>())
+            .and_then(|data| data.downcast_ref())
+    }
+
+    pub fn with<T>(mut self, data: T) -> Self
+    where
+        T: Send + Sync + 'static,
+    {
+        self.data.insert(TypeId::of::<T>(), Arc::new(data));
+        self
+    }
+}
+
+impl Debug for RenderContext {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("RenderContext")
+            .field("path", &self.path)
+            .field("locale", &self.locale)
+            .field("nonce", &self.nonce)
+            .field("csrf_token", &self.csrf_token)
+            .field("data", &self.data.len())
+            .finish()
+    }
+}
+
+struct User<T>(T);
+
+fn parse_locale(header: &str) -> Option<String> {
+    header
+        .split(',')
+        .filter_map(|item| item.split(';').next())
+        .map(str::trim)
+        .find(|tag| !tag.is_empty() && *tag != "*")
+        .map(str::to_owned)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Write;
+
+    use brace_web_core::http::header::ACCEPT_LANGUAGE;
+    use brace_web_core::test::TestRequest;
+
+    use super::RenderContext;
+    use crate::util::render::{render_with_context, Render, Renderer, RendererConfig, Result};
+
+    struct Account {
+        name: String,
+    }
+
+    struct Greeting;
+
+    impl Render for Greeting {
+        fn render(&self, renderer: &mut Renderer) -> Result {
+            let context = renderer.context();
+            let name = context
+                .user::<Account>()
+                .map(|account| account.name.clone())
+                .unwrap_or_else(|| "guest".to_owned());
+            let locale = context.locale().unwrap_or("en").to_owned();
+            let path = context.path().unwrap_or("/").to_owned();
+
+            Ok(write!(renderer, "{} {} {}", locale, name, path)?)
+        }
+    }
+
+    #[test]
+    fn test_context_from_request() {
+        let req = TestRequest::with_uri("/hello")
+            .header(ACCEPT_LANGUAGE, "fr-CH, fr;q=0.9, en;q=0.8")
+            .to_http_request();
+
+        req.extensions_mut().insert(
+            RenderContext::new()
+                .with_nonce("abc123")
+                .with_csrf_token("token")
+                .with_user(Account {
+                    name: "alice".to_owned(),
+                }),
+        );
+
+        let context = RenderContext::from_request(&req);
+
+        assert_eq!(context.path(), Some("/hello"));
+        assert_eq!(context.locale(), Some("fr-CH"));
+        assert_eq!(context.nonce(), Some("abc123"));
+        assert_eq!(context.csrf_token(), Some("token"));
+        assert_eq!(
+            render_with_context(&Greeting, &RendererConfig::default(), &context).unwrap(),
+            "fr-CH alice /hello"
+        );
+
+        let context = RenderContext::from_request(&TestRequest::default().to_http_request());
+
+        assert_eq!(context.locale(), None);
+        assert!(context.user::<Account>().is_none());
+        assert_eq!(
+            render_with_context(&Greeting, &RendererConfig::default(), &context).unwrap(),
+            "en guest /"
+        );
+    }
+}
@@ -0,0 +1,4 @@
+pub use brace_web_markup_escape::{
+    escape_attribute, escape_bogus_comment, escape_comment, escape_instruction, escape_raw_text,
+    escape_text, escape_url, is_valid_attribute, is_valid_tag, is_void,
+};
@@ -1,2 +1,11 @@
+pub mod cache;
+pub mod context;
+pub mod escape;
+pub mod negotiate;
 pub mod parser;
+pub mod partial;
 pub mod render;
+pub mod response;
+pub mod selector;
+pub mod stream;
+pub mod text;
@@ -0,0 +1,314 @@
+use brace_web_core::http::header::{ACCEPT, VARY};
+use brace_web_core::http::StatusCode;
+use brace_web_core::{HttpRequest, HttpResponse, Responder};
+use bytes::BytesMut;
+use futures::future::{self, Ready};
+use serde::Serialize;
+
+use crate::util::cache::CachePolicy;
+use crate::util::context::RenderContext;
+use crate::util::render::{Error, Render};
+use crate::util::stream::{render_body, respond_body};
+use crate::{Document, Element, Node, Nodes};
+
+const FRAGMENT_TYPE: &str = "text/fragment+html";
+
+pub trait Negotiate: Render + Serialize {
+    fn fragment(&self) -> Option<&Nodes> {
+        None
+    }
+}
+
+impl Negotiate for Document {
+    fn fragment(&self) -> Option<&Nodes> {
+        Some(self.body().map_or(self.nodes(), Element::nodes))
+    }
+}
+
+impl Negotiate for Element {}
+
+impl Negotiate for Node {}
+
+impl Negotiate for Nodes {}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Html,
+    Json,
+    Fragment,
+}
+
+impl Format {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Html | Self::Fragment => "text/html; charset=utf-8",
+            Self::Json => "application/json",
+        }
+    }
+}
+
+pub struct Negotiated<T> {
+    item: T,
+    fragment_type: String,
+    policy: CachePolicy,
+}
+
+impl<T> Negotiated<T>
+where
+    T: Negotiate,
+{
+    pub fn new(item: T) -> Self {
+        Self {
+            item,
+            fragment_type: FRAGMENT_TYPE.to_owned(),
+            policy: CachePolicy::default(),
+        }
+    }
+
+    pub fn with_fragment_type<F>(mut self, fragment_type: F) -> Self
+    where
+        F: Into<String>,
+    {
+        self.fragment_type = fragment_type.into();
+        self
+    }
+
+    pub fn with_policy(mut self, policy: CachePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn format(&self, req: &HttpRequest) -> Option<Format> {
+        let accept = match req
+            .headers()
+            .get(ACCEPT)
+            .and_then(|value| value.to_str().ok())
+        {
+            Some(accept) if !accept.trim().is_empty() => accept,
+            _ => return Some(Format::Html),
+        };
+        let ranges = parse_accept(accept);
+        let candidates = [
+            (Format::Html, "text/html"),
+            (Format::Html, "application/xhtml+xml"),
+            (Format::Json, "application/json"),
+            (Format::Fragment, self.fragment_type.as_str()),
+        ];
+        let mut best: Option<(Format, f32)> = None;
+
+        for (format, media) in candidates.iter() {
+            let quality = quality(&ranges, media);
+
+            if quality > 0.0 && best.map_or(true, |(_, best)| quality > best) {
+                best = Some((*format, quality));
+            }
+        }
+
+        best.map(|(format, _)| format)
+    }
+
+    pub fn into_response(self, req: &HttpRequest) -> Result<HttpResponse, Error> {
+        let format = match self.format(req) {
+            Some(format) => format,
+            None => {
+                return Ok(HttpResponse::NotAcceptable()
+                    .header(VARY, "Accept")
+                    .finish())
+            }
+        };
+        let context = RenderContext::from_request(req);
+        let body = match (format, self.item.fragment()) {
+            (Format::Fragment, Some(nodes)) => render_body(nodes, &context)?,
+            (Format::Html, _) | (Format::Fragment, None) => render_body(&self.item, &context)?,
+            (Format::Json, _) => {
+                let mut buffer = BytesMut::new();
+
+                buffer.extend_from_slice(&serde_json::to_vec(&self.item)?);
+                buffer
+            }
+        };
+
+        Ok(respond_body(
+            req,
+            StatusCode::OK,
+            &self.policy,
+            format.content_type(),
+            body,
+            |builder| {
+                builder.header(VARY, "Accept");
+            },
+        ))
+    }
+}
+
+impl<T> Responder for Negotiated<T>
+where
+    T: Negotiate,
+{
+    type Error = Error;
+    type Future = Ready<Result<HttpResponse, Self::Error>>;
+
+    fn respond_to(self, req: &HttpRequest) -> Self::Future {
+        future::ready(self.into_response(req))
+    }
+}
+
+struct MediaRange<'a> {
+    kind: &'a str,
+    subtype: &'a str,
+    quality: f32,
+}
+
+fn parse_accept(accept: &str) -> Vec<MediaRange<'_>> {
+    accept
+        .split(',')
+        .filter_map(|item| {
+            let mut params = item.split(';').map(str::trim);
+            let mut media = params.next()?.splitn(2, '/');
+            let kind = media.next()?.trim();
+            let subtype = media.next()?.trim();
+            let quality = params
+                .filter(|param| param.starts_with("q="))
+                .find_map(|param| param[2..].parse().ok())
+                .unwrap_or(1.0);
+
+            Some(MediaRange {
+                kind,
+                subtype,
+                quality,
+            })
+        })
+        .collect()
+}
+
+fn quality(ranges: &[MediaRange], media: &str) -> f32 {
+    let media = media.split(';').next().unwrap_or(media).trim();
+    let (kind, subtype) = match media.find('/') {
+        Some(index) => (&media[..index], &media[index + 1..]),
+        None => return 0.0,
+    };
+    let mut best = (0, 0.0);
+
+    for range in ranges {
+        let specificity = if range.kind.eq_ignore_ascii_case(kind)
+            && range.subtype.eq_ignore_ascii_case(subtype)
+        {
+            3
+        } else if range.kind.eq_ignore_ascii_case(kind) && range.subtype == "*" {
+            2
+        } else if range.kind == "*" && range.subtype == "*" {
+            1
+        } else {
+            continue;
+        };
+
+        if specificity > best.0 {
+            best = (specificity, range.quality);
+        }
+    }
+
+    best.1
+}
+
+#[cfg(test)]
+mod tests {
+    use brace_web_core::dev::{BodySize, MessageBody};
+    use brace_web_core::http::header::{ACCEPT, CONTENT_TYPE, ETAG, IF_NONE_MATCH, VARY};
+    use brace_web_core::http::StatusCode;
+    use brace_web_core::test::TestRequest;
+    use brace_web_core::Responder;
+
+    use super::{Format, Negotiated};
+    use crate::{Document, Element};
+
+    fn document() -> Document {
+        Document::new().with_node(
+            Element::new("html")
+                .with_node(Element::new("head").with_node(Element::new("title").with_node("Hello")))
+                .with_node(
+                    Element::new("body").with_node(Element::new("p").with_node("Hello world")),
+                ),
+        )
+    }
+
+    fn format(accept: &str) -> Option<Format> {
+        let req = TestRequest::default()
+            .header(ACCEPT, accept)
+            .to_http_request();
+
+        Negotiated::new(document()).format(&req)
+    }
+
+    #[test]
+    fn test_negotiate_format() {
+        assert_eq!(
+            Negotiated::new(document()).format(&TestRequest::default().to_http_request()),
+            Some(Format::Html)
+        );
+        assert_eq!(
+            format("text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8"),
+            Some(Format::Html)
+        );
+        assert_eq!(format("application/json"), Some(Format::Json));
+        assert_eq!(
+            format("application/json, text/html;q=0.5"),
+            Some(Format::Json)
+        );
+        assert_eq!(format("text/fragment+html"), Some(Format::Fragment));
+        assert_eq!(format("*/*"), Some(Format::Html));
+        assert_eq!(format("text/*, text/html;q=0"), Some(Format::Fragment));
+        assert_eq!(format("image/png"), None);
+    }
+
+    #[actix_rt::test]
+    async fn test_negotiate_response() {
+        let req = TestRequest::default()
+            .header(ACCEPT, "application/json")
+            .to_http_request();
+        let res = Negotiated::new(Element::new("p").with_node("Hello"))
+            .respond_to(&req)
+            .await
+            .unwrap();
+
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers().get(CONTENT_TYPE).unwrap(), "application/json");
+        assert_eq!(res.headers().get(VARY).unwrap(), "Accept");
+        assert_eq!(
+            res.body().size(),
+            BodySize::Sized(r#"{"tag":"p","attrs":{},"nodes":["Hello"]}"#.len() as _)
+        );
+
+        let req = TestRequest::default()
+            .header(ACCEPT, "application/json")
+            .header(IF_NONE_MATCH, res.headers().get(ETAG).unwrap().clone())
+            .to_http_request();
+        let res = Negotiated::new(Element::new("p").with_node("Hello"))
+            .respond_to(&req)
+            .await
+            .unwrap();
+
+        assert_eq!(res.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(res.headers().get(VARY).unwrap(), "Accept");
+
+        let req = TestRequest::default()
+            .header(ACCEPT, "text/fragment+html")
+            .to_http_request();
+        let res = Negotiated::new(document()).into_response(&req).unwrap();
+
+        assert_eq!(
+            res.headers().get(CONTENT_TYPE).unwrap(),
+            "text/html; charset=utf-8"
+        );
+        assert_eq!(
+            res.body().size(),
+            BodySize::Sized("<p>Hello world</p>".len() as _)
+        );
+
+        let req = TestRequest::default()
+            .header(ACCEPT, "image/png")
+            .to_http_request();
+        let res = Negotiated::new(document()).into_response(&req).unwrap();
+
+        assert_eq!(res.status(), StatusCode::NOT_ACCEPTABLE);
+    }
+}
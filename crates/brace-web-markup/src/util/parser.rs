@@ -1,6 +1,6 @@
 use brace_parser::prelude::*;
 
-use crate::{Attribute, Attributes, Document, Element, Node, Nodes, Text};
+use crate::{Attribute, Attributes, Comment, Document, Element, Node, Nodes, Raw, Text};
 
 pub fn document(input: &str) -> Output<Document> {
     parse(
@@ -70,12 +70,70 @@ pub fn boolean(input: &str) -> Output<bool> {
     )
 }
 
+pub fn number(input: &str) -> Output<f64> {
+    parse(
+        input,
+        context(
+            "number",
+            map(
+                consume((
+                    optional('-'),
+                    sequence::numeric,
+                    optional(('.', sequence::numeric)),
+                )),
+                |number: &str| number.parse().unwrap_or_default(),
+            ),
+        ),
+    )
+}
+
+pub fn tokens(input: &str) -> Output<Vec<String>> {
+    parse(
+        input,
+        context(
+            "tokens",
+            delimited(
+                '[',
+                fail(delimited(
+                    optional(sequence::whitespace),
+                    map(
+                        optional(list(
+                            string,
+                            (
+                                optional(sequence::whitespace),
+                                ',',
+                                optional(sequence::whitespace),
+                            ),
+                        )),
+                        Option::unwrap_or_default,
+                    ),
+                    optional(sequence::whitespace),
+                )),
+                fail(']'),
+            ),
+        ),
+    )
+}
+
+pub fn url(input: &str) -> Output<String> {
+    parse(
+        input,
+        context("url", delimited("url(", fail(string), fail(')'))),
+    )
+}
+
 pub fn node(input: &str) -> Output<Node> {
     parse(
         input,
         context(
             "node",
-            either(map(text, Node::text), map(element, Node::element)),
+            branch((
+                map(block, Node::text),
+                map(text, Node::text),
+                map(raw, Node::raw),
+                map(comment, Node::comment),
+                map(element, Node::element),
+            )),
         ),
     )
 }
@@ -83,33 +141,125 @@ pub fn node(input: &str) -> Output<Node> {
 pub fn nodes(input: &str) -> Output<Nodes> {
     parse(
         input,
-        context("nodes", map(list(node, newline), Nodes::from)),
+        context(
+            "nodes",
+            map(list(entry, newline), |entries: Vec<Option<Node>>| {
+                Nodes::from(entries.into_iter().flatten().collect::<Vec<_>>())
+            }),
+        ),
+    )
+}
+
+fn entry(input: &str) -> Output<Option<Node>> {
+    parse(input, either(map(discard, |_| None), map(node, Some)))
+}
+
+pub fn discard(input: &str) -> Output<&str> {
+    parse(
+        input,
+        context(
+            "discard",
+            leading("//", leading(peek(either(not('/'), end)), line)),
+        ),
+    )
+}
+
+pub fn comment(input: &str) -> Output<Comment> {
+    parse(
+        input,
+        context("comment", map(leading("///", line), Comment::from)),
+    )
+}
+
+fn line(input: &str) -> Output<&str> {
+    parse(
+        input,
+        map(
+            optional(escaped(
+                not(character::linebreak),
+                not(character::linebreak),
+            )),
+            |line| line.unwrap_or_default().trim(),
+        ),
     )
 }
 
 pub fn text(input: &str) -> Output<Text> {
+    parse(input, context("text", map(literal, Text::from)))
+}
+
+pub fn block(input: &str) -> Output<Text> {
     parse(
         input,
         context(
-            "text",
+            "block",
             map(
                 delimited(
-                    '"',
+                    "\"\"\"",
                     map(
-                        optional(unescape(
-                            escaped(not('"'), branch(('"', '\\'))),
-                            branch(('"', '\\')),
-                        )),
+                        optional(escaped(not("\"\"\""), not("\"\"\""))),
                         Option::unwrap_or_default,
                     ),
-                    fail('"'),
+                    fail("\"\"\""),
                 ),
-                Text::from,
+                |block| Text::preserved(dedent(block)),
             ),
         ),
     )
 }
 
+fn dedent(block: &str) -> String {
+    let block = if block.starts_with("\r\n") {
+        &block[2..]
+    } else if block.starts_with('\n') {
+        &block[1..]
+    } else {
+        return block.to_owned();
+    };
+    let mut lines = block.lines().collect::<Vec<_>>();
+
+    if lines.len() > 1 && lines.last().map_or(false, |line| line.trim().is_empty()) {
+        lines.pop();
+    }
+
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    lines
+        .iter()
+        .map(|line| line.get(indent..).unwrap_or_else(|| line.trim_start()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn raw(input: &str) -> Output<Raw> {
+    parse(
+        input,
+        context("raw", map(leading('!', fail(literal)), Raw::from)),
+    )
+}
+
+fn literal(input: &str) -> Output<String> {
+    parse(
+        input,
+        delimited(
+            '"',
+            map(
+                optional(unescape(
+                    escaped(not('"'), branch(('"', '\\'))),
+                    branch(('"', '\\')),
+                )),
+                Option::unwrap_or_default,
+            ),
+            fail('"'),
+        ),
+    )
+}
+
 pub fn element(input: &str) -> Output<Element> {
     parse(
         input,
@@ -185,10 +335,13 @@ pub fn attribute(input: &str) -> Output<Attribute> {
         input,
         context(
             "attribute",
-            either(
+            branch((
                 map(string, Attribute::string),
                 map(boolean, Attribute::boolean),
-            ),
+                map(number, Attribute::number),
+                map(tokens, Attribute::Tokens),
+                map(url, Attribute::url),
+            )),
         ),
     )
 }
@@ -341,10 +494,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_number() {
+        assert_eq!(
+            parse("", number),
+            Err(Error::expect(Sequence::Numeric)
+                .but_found_end()
+                .with_context("number"))
+        );
+        assert_eq!(parse("12", number), Ok((12.0, "")));
+        assert_eq!(parse("-1.5", number), Ok((-1.5, "")));
+        assert_eq!(parse("3.", number), Ok((3.0, ".")));
+    }
+
+    #[test]
+    fn test_tokens() {
+        assert_eq!(
+            parse("", tokens),
+            Err(Error::expect('[').but_found_end().with_context("tokens"))
+        );
+        assert_eq!(parse("[]", tokens), Ok((vec![], "")));
+        assert_eq!(
+            parse("[ \"one\", \"two\" ]", tokens),
+            Ok((vec!["one".to_owned(), "two".to_owned()], ""))
+        );
+        assert_eq!(
+            parse("[\"one\"", tokens),
+            Err(Error::expect(']').but_found_end().with_context("tokens"))
+        );
+    }
+
+    #[test]
+    fn test_url() {
+        assert_eq!(
+            parse("url(\"/index.html\")", url),
+            Ok(("/index.html".to_owned(), ""))
+        );
+        assert_eq!(
+            parse("url(\"/index.html\"", url),
+            Err(Error::expect(')').but_found_end().with_context("url"))
+        );
+    }
+
     #[test]
     fn test_node() {
         assert_eq!(parse("element", node), Ok((Node::element("element"), "")));
         assert_eq!(parse("\"text\"", node), Ok((Node::text("text"), "")));
+        assert_eq!(
+            parse("\"\"\" text \"\"\"", node),
+            Ok((Node::text(Text::preserved(" text ")), ""))
+        );
+        assert_eq!(
+            parse("!\"<b>text</b>\"", node),
+            Ok((Node::raw("<b>text</b>"), ""))
+        );
+        assert_eq!(parse("/// text", node), Ok((Node::comment("text"), "")));
         assert_eq!(
             parse("div { span | \"text\" }", node),
             Ok((
@@ -421,6 +625,83 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_nodes_comments() {
+        assert_eq!(
+            parse("// comment \n element", nodes),
+            Ok((Node::element("element").into(), ""))
+        );
+        assert_eq!(
+            parse("element \n // comment", nodes),
+            Ok((Node::element("element").into(), ""))
+        );
+        assert_eq!(
+            parse("element \n //\n element", nodes),
+            Ok((
+                vec![Node::element("element"), Node::element("element")].into(),
+                ""
+            ))
+        );
+        assert_eq!(
+            parse("/// comment \n element", nodes),
+            Ok((
+                vec![Node::comment("comment"), Node::element("element")].into(),
+                ""
+            ))
+        );
+        assert_eq!(
+            parse("div {\n // hidden\n /// shown\n \"text\"\n}", nodes),
+            Ok((
+                Element::new("div")
+                    .with_node(Node::comment("shown"))
+                    .with_node(Text::new("text"))
+                    .into(),
+                ""
+            ))
+        );
+    }
+
+    #[test]
+    fn test_discard() {
+        assert_eq!(
+            parse("", discard),
+            Err(Error::expect('/').but_found_end().with_context("discard"))
+        );
+        assert_eq!(parse("//", discard), Ok(("", "")));
+        assert_eq!(parse("// hello world", discard), Ok(("hello world", "")));
+        assert_eq!(
+            parse("// hello world\nelement", discard),
+            Ok(("hello world", "\nelement"))
+        );
+        assert_eq!(
+            parse("/// hello world", discard),
+            Err(Error::expect(Expect::End)
+                .but_found('/')
+                .with_context("discard"))
+        );
+    }
+
+    #[test]
+    fn test_comment() {
+        assert_eq!(
+            parse("", comment),
+            Err(Error::expect('/').but_found_end().with_context("comment"))
+        );
+        assert_eq!(parse("///", comment), Ok((Comment::from(""), "")));
+        assert_eq!(
+            parse("/// hello world", comment),
+            Ok((Comment::from("hello world"), ""))
+        );
+        assert_eq!(
+            parse("/// hello -- world \nelement", comment),
+            Ok((Comment::from("hello -- world"), "\nelement"))
+        );
+        assert_eq!(
+            parse("// hello world", comment),
+            Err(Error::expect('/').but_found(' ').with_context("comment"))
+        );
+    }
+
     #[test]
     fn test_text() {
         assert_eq!(
@@ -528,6 +809,100 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_block() {
+        assert_eq!(
+            parse("", block),
+            Err(Error::expect('"').but_found_end().with_context("block"))
+        );
+        assert_eq!(
+            parse("\"text\"", block),
+            Err(Error::expect('"').but_found('t').with_context("block"))
+        );
+        assert_eq!(parse("\"\"\"\"\"\"", block), Ok((Text::preserved(""), "")));
+        assert_eq!(
+            parse("\"\"\"  hello   world \"\"\"", block),
+            Ok((Text::preserved("  hello   world "), ""))
+        );
+        assert_eq!(
+            parse(
+                "\"\"\"\n    fn main() {\n        \"hello\";\n    }\n    \"\"\"",
+                block
+            ),
+            Ok((Text::preserved("fn main() {\n    \"hello\";\n}"), ""))
+        );
+        assert_eq!(
+            parse("\"\"\"hello", block),
+            Err(Error::expect('"')
+                .but_found_end()
+                .with_context("block")
+                .into_fail())
+        );
+    }
+
+    #[test]
+    fn test_nodes_text() {
+        assert_eq!(
+            parse("\"hello\" \n \"world\"", nodes),
+            Ok((vec![Node::text("hello"), Node::text("world")].into(), ""))
+        );
+        assert_eq!(
+            parse("\"hello\" \n // comment \n \"world\"", nodes),
+            Ok((vec![Node::text("hello"), Node::text("world")].into(), ""))
+        );
+        assert_eq!(
+            parse("\"hello\" \n em | \"big\" \n \"world\"", nodes),
+            Ok((
+                vec![
+                    Node::text("hello"),
+                    Element::new("em").with_node("big").into(),
+                    Node::text("world"),
+                ]
+                .into(),
+                ""
+            ))
+        );
+        assert_eq!(
+            parse("\"hello\" \n \"\"\" world \"\"\"", nodes),
+            Ok((
+                vec![Node::text("hello"), Node::text(Text::preserved(" world "))].into(),
+                ""
+            ))
+        );
+    }
+
+    #[test]
+    fn test_raw() {
+        assert_eq!(
+            parse("", raw),
+            Err(Error::expect('!').but_found_end().with_context("raw"))
+        );
+        assert_eq!(
+            parse("\"hello\"", raw),
+            Err(Error::expect('!').but_found('"').with_context("raw"))
+        );
+        assert_eq!(
+            parse("!hello", raw),
+            Err(Error::expect('"')
+                .but_found('h')
+                .with_context("raw")
+                .into_fail())
+        );
+        assert_eq!(parse("!\"\"", raw), Ok((Raw::from(""), "")));
+        assert_eq!(
+            parse("!\"<b>hello</b>\"", raw),
+            Ok((Raw::from("<b>hello</b>"), ""))
+        );
+        assert_eq!(
+            parse("!\"<p>\n  hello  world\n</p>\"", raw),
+            Ok((Raw::from("<p>\n  hello  world\n</p>"), ""))
+        );
+        assert_eq!(
+            parse("!\"<a href=\\\"#\\\">link</a>\"", raw),
+            Ok((Raw::from("<a href=\"#\">link</a>"), ""))
+        );
+    }
+
     #[test]
     fn test_element() {
         assert_eq!(
@@ -647,11 +1022,11 @@ mod tests {
     fn test_attribute() {
         assert_eq!(
             parse("", attribute),
-            Err(Error::expect('f').but_found_end().with_context("boolean"))
+            Err(Error::expect('u').but_found_end().with_context("url"))
         );
         assert_eq!(
             parse("hello world", attribute),
-            Err(Error::expect('f').but_found('h').with_context("boolean"))
+            Err(Error::expect('u').but_found('h').with_context("url"))
         );
         assert_eq!(
             parse("\"hello world\"", attribute),
@@ -662,6 +1037,15 @@ mod tests {
             parse("false", attribute),
             Ok((Attribute::boolean(false), ""))
         );
+        assert_eq!(parse("-2", attribute), Ok((Attribute::number(-2), "")));
+        assert_eq!(
+            parse("[\"a\", \"b\"]", attribute),
+            Ok((Attribute::tokens(vec!["a", "b"]), ""))
+        );
+        assert_eq!(
+            parse("url(\"/\")", attribute),
+            Ok((Attribute::url("/"), ""))
+        );
     }
 
     #[test]
@@ -0,0 +1,392 @@
+use std::collections::HashMap;
+
+use brace_web_core::http::header::{HeaderValue, VARY};
+use brace_web_core::http::StatusCode;
+use brace_web_core::web::Query;
+use brace_web_core::{HttpRequest, HttpResponse, Responder};
+use futures::future::{self, Ready};
+
+use crate::util::cache::CachePolicy;
+use crate::util::context::RenderContext;
+use crate::util::render::Error;
+use crate::util::selector::{Error as SelectorError, Selector};
+use crate::util::stream::{render_body, respond_body, respond_with};
+use crate::{Attribute, Document, Element, Nodes};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Target {
+    Id(String),
+    Selector(Selector),
+}
+
+impl Target {
+    pub fn id<T>(id: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self::Id(id.into())
+    }
+
+    pub fn selector<T>(selector: T) -> Result<Self, SelectorError>
+    where
+        T: AsRef<str>,
+    {
+        Ok(Self::Selector(Selector::parse(selector.as_ref())?))
+    }
+
+    pub fn find<'a>(&self, nodes: &'a Nodes) -> Option<&'a Element> {
+        match self {
+            Self::Id(id) => nodes
+                .elements()
+                .find(|element| has_attr(element, "id", |value| value == id)),
+            Self::Selector(selector) => selector.select(nodes).next(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Swap {
+    Inner,
+    Outer,
+}
+
+pub struct Partial {
+    document: Document,
+    header: String,
+    target_header: String,
+    param: Option<String>,
+    target: Option<Target>,
+    swap: Swap,
+    oob: Vec<Element>,
+    policy: CachePolicy,
+}
+
+impl Partial {
+    pub fn new(document: Document) -> Self {
+        Self {
+            document,
+            header: "HX-Request".to_owned(),
+            target_header: "HX-Target".to_owned(),
+            param: None,
+            target: None,
+            swap: Swap::Inner,
+            oob: Vec::new(),
+            policy: CachePolicy::default(),
+        }
+    }
+
+    pub fn with_header<T>(mut self, header: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.header = header.into();
+        self
+    }
+
+    pub fn with_target_header<T>(mut self, header: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.target_header = header.into();
+        self
+    }
+
+    pub fn with_param<T>(mut self, param: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.param = Some(param.into());
+        self
+    }
+
+    pub fn with_target(mut self, target: Target) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    pub fn with_swap(mut self, swap: Swap) -> Self {
+        self.swap = swap;
+        self
+    }
+
+    pub fn with_oob(mut self, element: Element) -> Self {
+        self.oob.push(element);
+        self
+    }
+
+    pub fn with_policy(mut self, policy: CachePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn is_partial(&self, req: &HttpRequest) -> bool {
+        let header = req
+            .headers()
+            .get(self.header.as_str())
+            .map_or(false, |value| value != "false");
+
+        header || self.param(req).is_some()
+    }
+
+    pub fn target(&self, req: &HttpRequest) -> Result<Option<Target>, SelectorError> {
+        if let Some(target) = &self.target {
+            return Ok(Some(target.clone()));
+        }
+
+        let header = req
+            .headers()
+            .get(self.target_header.as_str())
+            .and_then(|value| value.to_str().ok())
+            .filter(|value| !value.is_empty());
+
+        if let Some(id) = header {
+            return Ok(Some(Target::id(id)));
+        }
+
+        match self.param(req) {
+            Some(value) if value.starts_with('#') || value.starts_with('.') => {
+                Target::selector(value).map(Some)
+            }
+            Some(value) if !value.is_empty() => Ok(Some(Target::Id(value))),
+            _ => Ok(None),
+        }
+    }
+
+    pub fn fragment(&self, req: &HttpRequest) -> Result<Nodes, Error> {
+        let mut nodes = match self.target(req)? {
+            Some(target) => match (target.find(self.document.nodes()), self.swap) {
+                (Some(element), Swap::Outer) => Nodes::from(element.clone()),
+                (Some(element), Swap::Inner) => element.nodes().clone(),
+                (None, _) => return Err(Error::MissingTarget),
+            },
+            None => self
+                .document
+                .body()
+                .map_or(self.document.nodes(), Element::nodes)
+                .clone(),
+        };
+
+        for element in &self.oob {
+            let mut element = element.clone();
+
+            if element.get_attr("hx-swap-oob").is_none() {
+                element.set_attr("hx-swap-oob", "true");
+            }
+
+            nodes.append(element);
+        }
+
+        Ok(nodes)
+    }
+
+    pub fn into_response(self, req: &HttpRequest) -> Result<HttpResponse, Error> {
+        let vary = format!("{}, {}", self.header, self.target_header);
+
+        if !self.is_partial(req) {
+            let mut res = respond_with(self.document, req, &self.policy)?;
+
+            if let Ok(vary) = HeaderValue::from_str(&vary) {
+                res.headers_mut().insert(VARY, vary);
+            }
+
+            return Ok(res);
+        }
+
+        let body = render_body(&self.fragment(req)?, &RenderContext::from_request(req))?;
+
+        Ok(respond_body(
+            req,
+            StatusCode::OK,
+            &self.policy,
+            "text/html; charset=utf-8",
+            body,
+            |builder| {
+                builder.header(VARY, vary);
+            },
+        ))
+    }
+
+    fn param(&self, req: &HttpRequest) -> Option<String> {
+        let param = self.param.as_ref()?;
+        let query = Query::<HashMap<String, String>>::from_query(req.query_string()).ok()?;
+
+        query.into_inner().remove(param)
+    }
+}
+
+impl Responder for Partial {
+    type Error = Error;
+    type Future = Ready<Result<HttpResponse, Self::Error>>;
+
+    fn respond_to(self, req: &HttpRequest) -> Self::Future {
+        future::ready(self.into_response(req))
+    }
+}
+
+impl From<Document> for Partial {
+    fn from(from: Document) -> Self {
+        Self::new(from)
+    }
+}
+
+fn has_attr<F>(element: &Element, key: &str, f: F) -> bool
+where
+    F: FnOnce(&str) -> bool,
+{
+    element
+        .get_attr(key)
+        .and_then(Attribute::as_string)
+        .map_or(false, |value| f(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use brace_web_core::http::header::VARY;
+    use brace_web_core::http::StatusCode;
+    use brace_web_core::test::TestRequest;
+    use brace_web_core::ResponseError;
+
+    use super::{Partial, Swap, Target};
+    use crate::util::render::{render, Error};
+    use crate::{Document, Element};
+
+    fn document() -> Document {
+        Document::new().with_node(
+            Element::new("html").with_node(
+                Element::new("body")
+                    .with_node(Element::new("nav").with_attr("id", "nav").with_node("Menu"))
+                    .with_node(
+                        Element::new("main")
+                            .with_attr("id", "content")
+                            .with_attr("class", "page wide")
+                            .with_node(Element::new("p").with_node("Hello")),
+                    ),
+            ),
+        )
+    }
+
+    #[test]
+    fn test_partial_target() {
+        let req = TestRequest::default()
+            .header("HX-Request", "true")
+            .header("HX-Target", "content")
+            .to_http_request();
+        let partial = Partial::new(document());
+
+        assert!(partial.is_partial(&req));
+        assert_eq!(partial.target(&req), Ok(Some(Target::id("content"))));
+        assert_eq!(
+            render(&partial.fragment(&req).unwrap()).unwrap(),
+            "<p>Hello</p>"
+        );
+
+        let partial = Partial::new(document())
+            .with_target(Target::selector("main.page#content").unwrap())
+            .with_swap(Swap::Outer)
+            .with_oob(
+                Element::new("div")
+                    .with_attr("id", "flash")
+                    .with_node("Saved"),
+            );
+
+        assert_eq!(
+            render(&partial.fragment(&req).unwrap()).unwrap(),
+            "<main id=\"content\" class=\"page wide\"><p>Hello</p></main>\
+             <div id=\"flash\" hx-swap-oob=\"true\">Saved</div>"
+        );
+
+        let req = TestRequest::with_uri("/?fragment=%23nav").to_http_request();
+        let partial = Partial::new(document()).with_param("fragment");
+
+        assert!(partial.is_partial(&req));
+        assert_eq!(
+            partial.target(&req),
+            Ok(Some(Target::selector("#nav").unwrap()))
+        );
+        assert_eq!(render(&partial.fragment(&req).unwrap()).unwrap(), "Menu");
+
+        let req = TestRequest::default()
+            .header("HX-Request", "true")
+            .to_http_request();
+
+        assert_eq!(
+            render(&Partial::new(document()).fragment(&req).unwrap()).unwrap(),
+            "<nav id=\"nav\">Menu</nav><main id=\"content\" class=\"page wide\"><p>Hello</p></main>"
+        );
+
+        let partial = Partial::new(document())
+            .with_target(Target::selector("body > main > p").unwrap())
+            .with_swap(Swap::Outer);
+
+        assert_eq!(
+            render(&partial.fragment(&req).unwrap()).unwrap(),
+            "<p>Hello</p>"
+        );
+        assert!(Target::selector("main >").is_err());
+
+        let req = TestRequest::default()
+            .header("HX-Request", "true")
+            .header("HX-Target", "missing")
+            .to_http_request();
+
+        match Partial::new(document()).fragment(&req) {
+            Err(Error::MissingTarget) => (),
+            _ => panic!("expected missing target error"),
+        }
+
+        let req = TestRequest::with_uri("/?fragment=.%3E").to_http_request();
+
+        assert!(Partial::new(document())
+            .with_param("fragment")
+            .fragment(&req)
+            .is_err());
+    }
+
+    #[actix_rt::test]
+    async fn test_partial_response() {
+        let req = TestRequest::default().to_http_request();
+        let partial = Partial::new(document());
+
+        assert!(!partial.is_partial(&req));
+
+        let res = partial.into_response(&req).unwrap();
+
+        assert_eq!(res.headers().get(VARY).unwrap(), "HX-Request, HX-Target");
+
+        let req = TestRequest::default()
+            .header("X-Partial", "1")
+            .to_http_request();
+        let partial = Partial::new(document()).with_header("X-Partial");
+
+        assert!(partial.is_partial(&req));
+        assert_eq!(
+            partial
+                .into_response(&req)
+                .unwrap()
+                .headers()
+                .get(VARY)
+                .unwrap(),
+            "X-Partial, HX-Target"
+        );
+
+        let req = TestRequest::with_uri("/?fragment=%23").to_http_request();
+
+        match Partial::new(document())
+            .with_param("fragment")
+            .into_response(&req)
+        {
+            Err(error) => assert_eq!(error.status_code(), StatusCode::BAD_REQUEST),
+            Ok(_) => panic!("expected invalid selector error"),
+        }
+
+        let req = TestRequest::default()
+            .header("HX-Request", "true")
+            .header("HX-Target", "missing")
+            .to_http_request();
+
+        match Partial::new(document()).into_response(&req) {
+            Err(error) => assert_eq!(error.status_code(), StatusCode::NOT_FOUND),
+            Ok(_) => panic!("expected missing target error"),
+        }
+    }
+}
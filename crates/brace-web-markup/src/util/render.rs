@@ -1,17 +1,77 @@
 use std::error::Error as StdError;
 use std::fmt::{Display, Error as FmtError, Formatter, Result as FmtResult, Write};
+use std::io::{Error as IoError, Write as IoWrite};
 use std::result::Result as StdResult;
 
 use brace_web_core::dev::HttpResponseBuilder;
+use brace_web_core::http::StatusCode;
 use brace_web_core::{HttpResponse, ResponseError};
+use bytes::BytesMut;
+use once_cell::sync::Lazy;
+use serde_json::Error as JsonError;
+
+use crate::util::context::{RenderContext, CONTEXT};
+use crate::util::selector::Error as SelectorError;
+use crate::{Element, Node, Nodes};
+
+pub(crate) static CONFIG: Lazy<RendererConfig> = Lazy::new(RendererConfig::default);
 
 pub type Result = StdResult<(), Error>;
 
 pub fn render<T>(item: &T) -> StdResult<String, Error>
+where
+    T: Render,
+{
+    render_with(item, &CONFIG)
+}
+
+pub fn render_with<T>(item: &T, config: &RendererConfig) -> StdResult<String, Error>
 where
     T: Render,
 {
     let mut buffer = String::new();
+    let mut renderer = Renderer::with_config(&mut buffer, config);
+
+    renderer.render(item)?;
+
+    Ok(buffer)
+}
+
+pub fn render_with_context<T>(
+    item: &T,
+    config: &RendererConfig,
+    context: &RenderContext,
+) -> StdResult<String, Error>
+where
+    T: Render,
+{
+    let mut buffer = String::new();
+    let mut renderer = Renderer::with_config(&mut buffer, config).with_context(context);
+
+    renderer.render(item)?;
+
+    Ok(buffer)
+}
+
+pub fn render_to<T, W>(item: &T, writer: W) -> Result
+where
+    T: Render,
+    W: IoWrite,
+{
+    let mut writer = IoWriter::new(writer);
+    let result = Renderer::new(&mut writer).render(item);
+
+    match writer.error {
+        Some(error) => Err(Error::Io(error)),
+        None => result,
+    }
+}
+
+pub fn render_bytes<T>(item: &T) -> StdResult<BytesMut, Error>
+where
+    T: Render,
+{
+    let mut buffer = BytesMut::with_capacity(item.size_hint());
     let mut renderer = Renderer::new(&mut buffer);
 
     renderer.render(item)?;
@@ -21,16 +81,50 @@ where
 
 pub trait Render {
     fn render(&self, renderer: &mut Renderer) -> Result;
+
+    fn size_hint(&self) -> usize {
+        0
+    }
 }
 
-pub struct Renderer<'a>(&'a mut (dyn Write + 'a));
+pub struct Renderer<'a> {
+    buffer: &'a mut (dyn Write + 'a),
+    config: &'a RendererConfig,
+    context: &'a RenderContext,
+    pub(crate) state: State,
+}
 
 impl<'a> Renderer<'a> {
     pub fn new<T>(buffer: &'a mut T) -> Self
     where
         T: Write,
     {
-        Self(buffer)
+        Self::with_config(buffer, &CONFIG)
+    }
+
+    pub fn with_config<T>(buffer: &'a mut T, config: &'a RendererConfig) -> Self
+    where
+        T: Write,
+    {
+        Self {
+            buffer,
+            config,
+            context: &CONTEXT,
+            state: State::default(),
+        }
+    }
+
+    pub fn with_context(mut self, context: &'a RenderContext) -> Self {
+        self.context = context;
+        self
+    }
+
+    pub fn config(&self) -> &RendererConfig {
+        self.config
+    }
+
+    pub fn context(&self) -> &RenderContext {
+        self.context
     }
 
     pub fn render<T>(&mut self, item: &T) -> Result
@@ -38,12 +132,521 @@ impl<'a> Renderer<'a> {
         T: Render,
     {
         item.render(self)
+            .map_err(|error| self.limit_error(error, Vec::new()))
+    }
+
+    pub fn written(&self) -> usize {
+        self.state.written
+    }
+
+    pub fn is_pretty(&self) -> bool {
+        self.config.pretty && !self.state.scope.inline
+    }
+
+    pub fn is_inline(&self, tag: &str) -> bool {
+        self.config.is_inline(tag)
+    }
+
+    pub fn is_xml(&self) -> bool {
+        self.config.is_xml()
+    }
+
+    pub fn is_minify(&self) -> bool {
+        self.config.is_minify()
+    }
+
+    pub fn in_raw_text(&self) -> bool {
+        self.state.scope.raw
+    }
+
+    pub fn newline(&mut self) -> Result {
+        if self.is_pretty() {
+            let newline = self.config.newline.as_str();
+
+            self.write_str(newline)?;
+
+            for _ in 0..self.state.depth * self.config.indent {
+                self.write_char(' ')?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn enter(&mut self, layout: Layout, raw: bool) -> Scope {
+        let scope = self.state.scope;
+
+        match layout {
+            Layout::Inline => self.state.scope.inline = true,
+            Layout::Block => self.state.depth += 1,
+            Layout::Compact => (),
+        }
+
+        self.state.scope.raw = raw;
+
+        scope
+    }
+
+    pub(crate) fn leave(&mut self, layout: Layout, scope: Scope) -> Result {
+        self.state.scope = scope;
+
+        if let Layout::Block = layout {
+            self.state.depth -= 1;
+            self.newline()?;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn open_node(&mut self, element: bool) -> Result {
+        self.state.nodes += 1;
+
+        match self.config.max_nodes {
+            Some(limit) if self.state.nodes > limit => {
+                return Err(Error::NodeLimit {
+                    limit,
+                    path: Vec::new(),
+                })
+            }
+            _ => (),
+        }
+
+        if !element {
+            return Ok(());
+        }
+
+        self.state.nesting += 1;
+
+        match self.config.max_depth {
+            Some(limit) if self.state.nesting > limit => Err(Error::DepthLimit {
+                limit,
+                path: Vec::new(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn close_node(&mut self) {
+        self.state.nesting -= 1;
+    }
+
+    pub(crate) fn walk(&mut self, nodes: &Nodes, parent: Option<&Element>, frame: Frame) -> Result {
+        let mut stack = vec![frame];
+        let mut elements: Vec<&Element> = Vec::new();
+
+        loop {
+            let (nodes, parent) = match elements.last() {
+                Some(element) => (element.nodes(), Some(*element)),
+                None => (nodes, parent),
+            };
+
+            match step(nodes, parent, &mut stack, self)? {
+                Step::Enter(element) => elements.push(element),
+                Step::Next => (),
+                Step::Leave => match elements.pop() {
+                    Some(element) => ascend(element, &mut stack, self)?,
+                    None => return Ok(()),
+                },
+            }
+        }
+    }
+
+    fn limit_error(&self, error: Error, path: Vec<usize>) -> Error {
+        match error {
+            Error::Format(_) if self.state.exceeded => Error::OutputLimit {
+                limit: self.config.max_output.unwrap_or_default(),
+                path,
+            },
+            Error::DepthLimit { limit, path: empty } if empty.is_empty() => {
+                Error::DepthLimit { limit, path }
+            }
+            Error::NodeLimit { limit, path: empty } if empty.is_empty() => {
+                Error::NodeLimit { limit, path }
+            }
+            error => error,
+        }
     }
 }
 
 impl Write for Renderer<'_> {
     fn write_str(&mut self, s: &str) -> FmtResult {
-        self.0.write_str(s)
+        match self.config.max_output {
+            Some(limit) if self.state.written + s.len() > limit => {
+                self.state.exceeded = true;
+
+                Err(FmtError)
+            }
+            _ => {
+                self.state.written += s.len();
+                self.buffer.write_str(s)
+            }
+        }
+    }
+}
+
+pub(crate) struct Frame {
+    index: usize,
+    layout: Layout,
+    leading: bool,
+    inline: bool,
+    omit: bool,
+    restore: Scope,
+}
+
+impl Frame {
+    pub(crate) fn root(renderer: &Renderer, leading: bool) -> Self {
+        let layout = if renderer.is_pretty() {
+            Layout::Block
+        } else {
+            Layout::Compact
+        };
+
+        Self::new(layout, leading, renderer.state.scope)
+    }
+
+    pub(crate) fn new(layout: Layout, leading: bool, restore: Scope) -> Self {
+        Self {
+            index: 0,
+            layout,
+            leading,
+            inline: false,
+            omit: false,
+            restore,
+        }
+    }
+
+    pub(crate) fn index(&self) -> usize {
+        self.index
+    }
+}
+
+pub(crate) enum Step<'a> {
+    Enter(&'a Element),
+    Next,
+    Leave,
+}
+
+pub(crate) fn step<'a>(
+    nodes: &'a Nodes,
+    parent: Option<&'a Element>,
+    stack: &mut Vec<Frame>,
+    renderer: &mut Renderer,
+) -> StdResult<Step<'a>, Error> {
+    step_inner(nodes, parent, stack, renderer)
+        .map_err(|error| renderer.limit_error(error, path(stack)))
+}
+
+fn step_inner<'a>(
+    nodes: &'a Nodes,
+    parent: Option<&'a Element>,
+    stack: &mut Vec<Frame>,
+    renderer: &mut Renderer,
+) -> StdResult<Step<'a>, Error> {
+    let minify = renderer.is_minify();
+    let frame = match stack.last_mut() {
+        Some(frame) => frame,
+        None => return Ok(Step::Leave),
+    };
+
+    if minify && nodes.is_collapsible(frame.index, parent, renderer) {
+        frame.index += 1;
+
+        return Ok(Step::Next);
+    }
+
+    let node = match nodes.get(frame.index) {
+        Some(node) => node,
+        None => return Ok(Step::Leave),
+    };
+
+    renderer.open_node(node.is_element())?;
+
+    if let Layout::Block = frame.layout {
+        let is_inline = node.is_inline(renderer);
+
+        if (frame.leading || frame.index > 0) && !(frame.inline && is_inline) {
+            renderer.newline()?;
+        }
+
+        frame.inline = is_inline;
+    }
+
+    let element = match node {
+        Node::Element(element) => element,
+        node => {
+            node.render(renderer)?;
+            frame.index += 1;
+
+            return Ok(Step::Next);
+        }
+    };
+
+    element.render_open(renderer)?;
+
+    if element.is_self_closing(renderer) {
+        renderer.close_node();
+        frame.index += 1;
+
+        return Ok(Step::Next);
+    }
+
+    let omit = minify && element.omits_end(nodes.get(frame.index + 1), parent);
+    let layout = element.layout(renderer);
+    let restore = renderer.enter(layout, element.is_raw_text(renderer));
+
+    stack.push(Frame {
+        omit,
+        ..Frame::new(layout, true, restore)
+    });
+
+    Ok(Step::Enter(element))
+}
+
+pub(crate) fn ascend(element: &Element, stack: &mut Vec<Frame>, renderer: &mut Renderer) -> Result {
+    let frame = match stack.pop() {
+        Some(frame) => frame,
+        None => return Ok(()),
+    };
+    let mut result = renderer.leave(frame.layout, frame.restore);
+
+    if result.is_ok() && !frame.omit {
+        result = element.render_close(renderer);
+    }
+
+    renderer.close_node();
+
+    let result = result.map_err(|error| renderer.limit_error(error, path(stack)));
+
+    if let Some(parent) = stack.last_mut() {
+        parent.index += 1;
+    }
+
+    result
+}
+
+fn path(stack: &[Frame]) -> Vec<usize> {
+    stack.iter().map(|frame| frame.index).collect()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Layout {
+    Compact,
+    Inline,
+    Block,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Scope {
+    inline: bool,
+    raw: bool,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct State {
+    depth: usize,
+    scope: Scope,
+    nesting: usize,
+    nodes: usize,
+    written: usize,
+    exceeded: bool,
+}
+
+struct IoWriter<W> {
+    writer: W,
+    error: Option<IoError>,
+}
+
+impl<W> IoWriter<W> {
+    fn new(writer: W) -> Self {
+        Self {
+            writer,
+            error: None,
+        }
+    }
+}
+
+impl<W> Write for IoWriter<W>
+where
+    W: IoWrite,
+{
+    fn write_str(&mut self, s: &str) -> FmtResult {
+        self.writer.write_all(s.as_bytes()).map_err(|error| {
+            self.error = Some(error);
+            FmtError
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RendererConfig {
+    syntax: Syntax,
+    declaration: bool,
+    pretty: bool,
+    minify: bool,
+    max_depth: Option<usize>,
+    max_output: Option<usize>,
+    max_nodes: Option<usize>,
+    indent: usize,
+    newline: Newline,
+    inline: Vec<String>,
+    raw_text: Vec<String>,
+}
+
+impl RendererConfig {
+    pub fn compact() -> Self {
+        Self::default()
+    }
+
+    pub fn pretty() -> Self {
+        Self {
+            pretty: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn minify() -> Self {
+        Self {
+            minify: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn xml() -> Self {
+        Self {
+            syntax: Syntax::Xml,
+            ..Self::default()
+        }
+    }
+
+    pub fn syntax(mut self, syntax: Syntax) -> Self {
+        self.syntax = syntax;
+        self
+    }
+
+    pub fn declaration(mut self, declaration: bool) -> Self {
+        self.declaration = declaration;
+        self
+    }
+
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    pub fn max_output(mut self, max_output: usize) -> Self {
+        self.max_output = Some(max_output);
+        self
+    }
+
+    pub fn max_nodes(mut self, max_nodes: usize) -> Self {
+        self.max_nodes = Some(max_nodes);
+        self
+    }
+
+    pub fn indent(mut self, indent: usize) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    pub fn newline(mut self, newline: Newline) -> Self {
+        self.newline = newline;
+        self
+    }
+
+    pub fn inline<T>(mut self, tag: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.inline.push(tag.into());
+        self
+    }
+
+    pub fn raw_text<T>(mut self, tag: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.raw_text.push(tag.into());
+        self
+    }
+
+    pub fn is_pretty(&self) -> bool {
+        self.pretty
+    }
+
+    pub fn is_xml(&self) -> bool {
+        self.syntax == Syntax::Xml
+    }
+
+    pub fn is_minify(&self) -> bool {
+        self.minify && !self.is_xml()
+    }
+
+    pub fn has_declaration(&self) -> bool {
+        self.is_xml() && self.declaration
+    }
+
+    pub fn is_inline(&self, tag: &str) -> bool {
+        match tag {
+            "a" | "abbr" | "b" | "bdi" | "bdo" | "br" | "button" | "cite" | "code" | "data"
+            | "del" | "dfn" | "em" | "i" | "img" | "input" | "ins" | "kbd" | "label" | "mark"
+            | "meter" | "output" | "progress" | "q" | "ruby" | "s" | "samp" | "select"
+            | "small" | "span" | "strong" | "sub" | "sup" | "textarea" | "time" | "u" | "var"
+            | "wbr" => true,
+            _ => self.inline.iter().any(|inline| inline == tag),
+        }
+    }
+
+    pub fn is_raw_text(&self, tag: &str) -> bool {
+        match tag {
+            "script" | "style" => true,
+            _ => self.raw_text.iter().any(|raw_text| raw_text == tag),
+        }
+    }
+
+    pub fn is_preformatted(&self, tag: &str) -> bool {
+        match tag {
+            "pre" | "textarea" | "listing" | "plaintext" => true,
+            _ => self.is_raw_text(tag),
+        }
+    }
+}
+
+impl Default for RendererConfig {
+    fn default() -> Self {
+        Self {
+            syntax: Syntax::Html,
+            declaration: true,
+            pretty: false,
+            minify: false,
+            max_depth: None,
+            max_output: None,
+            max_nodes: None,
+            indent: 2,
+            newline: Newline::Lf,
+            inline: Vec::new(),
+            raw_text: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Syntax {
+    Html,
+    Xml,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Newline {
+    Lf,
+    CrLf,
+}
+
+impl Newline {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Lf => "\n",
+            Self::CrLf => "\r\n",
+        }
     }
 }
 
@@ -51,6 +654,17 @@ impl Write for Renderer<'_> {
 pub enum Error {
     Message(String),
     Format(FmtError),
+    InvalidTag(String),
+    InvalidAttribute(String),
+    InvalidInstruction(String),
+    InvalidHeader(String),
+    InvalidSelector(SelectorError),
+    MissingTarget,
+    DepthLimit { limit: usize, path: Vec<usize> },
+    OutputLimit { limit: usize, path: Vec<usize> },
+    NodeLimit { limit: usize, path: Vec<usize> },
+    Io(IoError),
+    Json(JsonError),
 }
 
 impl Display for Error {
@@ -58,13 +672,61 @@ impl Display for Error {
         match self {
             Self::Message(message) => write!(f, "{}", message),
             Self::Format(error) => write!(f, "{}", error),
+            Self::InvalidTag(tag) => write!(f, "Invalid tag name: {:?}", tag),
+            Self::InvalidAttribute(key) => write!(f, "Invalid attribute name: {:?}", key),
+            Self::InvalidInstruction(target) => {
+                write!(f, "Invalid processing instruction target: {:?}", target)
+            }
+            Self::InvalidHeader(name) => write!(f, "Invalid header: {:?}", name),
+            Self::InvalidSelector(error) => write!(f, "Invalid selector: {}", error),
+            Self::MissingTarget => write!(f, "Partial target not found"),
+            Self::DepthLimit { limit, path } => write!(
+                f,
+                "Nesting depth limit of {} exceeded at {}",
+                limit,
+                display_path(path)
+            ),
+            Self::OutputLimit { limit, path } => write!(
+                f,
+                "Output limit of {} bytes exceeded at {}",
+                limit,
+                display_path(path)
+            ),
+            Self::NodeLimit { limit, path } => write!(
+                f,
+                "Node limit of {} exceeded at {}",
+                limit,
+                display_path(path)
+            ),
+            Self::Io(error) => write!(f, "{}", error),
+            Self::Json(error) => write!(f, "{}", error),
         }
     }
 }
 
+fn display_path(path: &[usize]) -> String {
+    if path.is_empty() {
+        return "/".to_owned();
+    }
+
+    path.iter().fold(String::new(), |mut output, index| {
+        output.push('/');
+        output.push_str(&index.to_string());
+        output
+    })
+}
+
 impl StdError for Error {}
 
 impl ResponseError for Error {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::InvalidSelector(_) => StatusCode::BAD_REQUEST,
+            Self::MissingTarget => StatusCode::NOT_FOUND,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
     fn error_response(&self) -> HttpResponse {
         HttpResponseBuilder::new(self.status_code())
             .content_type("text/html; charset=utf-8")
@@ -72,16 +734,40 @@ impl ResponseError for Error {
     }
 }
 
+impl From<SelectorError> for Error {
+    fn from(from: SelectorError) -> Self {
+        Self::InvalidSelector(from)
+    }
+}
+
 impl From<FmtError> for Error {
     fn from(from: FmtError) -> Self {
         Self::Format(from)
     }
 }
 
+impl From<IoError> for Error {
+    fn from(from: IoError) -> Self {
+        Self::Io(from)
+    }
+}
+
+impl From<JsonError> for Error {
+    fn from(from: JsonError) -> Self {
+        Self::Json(from)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::render;
-    use crate::{Element, Node};
+    use std::io::{Error as IoError, ErrorKind, Result as IoResult, Write};
+
+    use super::{
+        render, render_bytes, render_to, render_with, Error, Newline, RendererConfig, Syntax,
+    };
+    use crate::tree::element::namespace;
+    use crate::util::render::Render;
+    use crate::{Document, Element, Instruction, Node, Nodes, Text};
 
     #[test]
     fn test_render_node() {
@@ -142,4 +828,362 @@ mod tests {
             "<input type=\"checkbox\" checked />"
         );
     }
+
+    #[test]
+    fn test_render_escape() {
+        let node_1 = Node::text("<script>alert(\"x & y\")</script>");
+
+        assert_eq!(
+            render(&node_1).unwrap(),
+            "&lt;script&gt;alert(\"x &amp; y\")&lt;/script&gt;"
+        );
+
+        let node_2 = Element::new("a")
+            .with_attr("href", "/search?a=1&b=\"2\"")
+            .with_attr("title", "<b>'bold'</b>")
+            .with_node(Text::new("Tom & Jerry"));
+
+        assert_eq!(
+            render(&node_2).unwrap(),
+            "<a href=\"/search?a=1&amp;b=&quot;2&quot;\" title=\"&lt;b&gt;&#39;bold&#39;&lt;/b&gt;\">Tom &amp; Jerry</a>"
+        );
+    }
+
+    #[test]
+    fn test_render_invalid_names() {
+        let node_1 = Element::new("div onclick=alert(1)");
+
+        match render(&node_1) {
+            Err(Error::InvalidTag(tag)) => assert_eq!(tag, "div onclick=alert(1)"),
+            _ => panic!("expected invalid tag error"),
+        }
+
+        let node_2 = Element::new("div").with_attr("x onclick", "alert(1)");
+
+        match render(&node_2) {
+            Err(Error::InvalidAttribute(key)) => assert_eq!(key, "x onclick"),
+            _ => panic!("expected invalid attribute error"),
+        }
+    }
+
+    #[test]
+    fn test_render_comment() {
+        let node = Element::new("div")
+            .with_node(Node::comment("begin -- content"))
+            .with_node(Text::new("hello world"));
+
+        assert_eq!(
+            render(&node).unwrap(),
+            "<div><!--begin - - content-->hello world</div>"
+        );
+    }
+
+    #[test]
+    fn test_render_pretty() {
+        let document = Document::new().with_node(Element::new("html").with_nodes(vec![
+                Element::new("head")
+                    .with_node(Element::new("title").with_node("Hello world"))
+                    .into(),
+                Element::new("body")
+                    .with_nodes(vec![
+                        Element::new("p")
+                            .with_node("Hello")
+                            .with_node(Element::new("em").with_node("world"))
+                            .into(),
+                        Element::new("pre").with_node("one").into(),
+                        Element::new("hr").into(),
+                        Element::new("div").into(),
+                    ])
+                    .into(),
+            ]));
+
+        assert_eq!(
+            render_with(&document, &RendererConfig::pretty()).unwrap(),
+            "<!DOCTYPE html>
+<html>
+  <head>
+    <title>Hello world</title>
+  </head>
+  <body>
+    <p>Hello<em>world</em></p>
+    <pre>one</pre>
+    <hr />
+    <div></div>
+  </body>
+</html>"
+        );
+
+        assert_eq!(
+            render(&document).unwrap(),
+            "<!DOCTYPE html><html><head><title>Hello world</title></head><body><p>Hello<em>world</em></p><pre>one</pre><hr /><div></div></body></html>"
+        );
+    }
+
+    #[test]
+    fn test_render_pretty_inline() {
+        let element = Element::new("div").with_nodes(vec![
+            Node::text("Hello"),
+            Element::new("span").with_node("big").into(),
+            Element::new("a")
+                .with_node(Element::new("div").with_node(Element::new("p").with_node("x")))
+                .into(),
+            Element::new("p").with_node("block").into(),
+            Node::text("world"),
+        ]);
+
+        assert_eq!(
+            render_with(&element, &RendererConfig::pretty()).unwrap(),
+            "<div>
+  Hello<span>big</span><a><div><p>x</p></div></a>
+  <p>block</p>
+  world
+</div>"
+        );
+
+        let config = RendererConfig::pretty()
+            .indent(4)
+            .newline(Newline::CrLf)
+            .inline("x-icon");
+        let element = Element::new("ul").with_node(
+            Element::new("li")
+                .with_node(Element::new("x-icon"))
+                .with_node("Item"),
+        );
+
+        assert_eq!(
+            render_with(&element, &config).unwrap(),
+            "<ul>\r\n    <li><x-icon></x-icon>Item</li>\r\n</ul>"
+        );
+    }
+
+    #[test]
+    fn test_render_pretty_nodes() {
+        let nodes = Nodes::from(vec![
+            Element::new("header"),
+            Element::new("main").with_node(Element::new("p")),
+        ]);
+
+        assert_eq!(
+            render_with(&nodes, &RendererConfig::pretty()).unwrap(),
+            "<header></header>
+<main>
+  <p></p>
+</main>"
+        );
+    }
+
+    #[test]
+    fn test_render_minify() {
+        let document = Document::new().with_node(
+            Element::new("html")
+                .with_attr("lang", "en")
+                .with_node(Element::new("head").with_node(Element::new("title").with_node("Hi")))
+                .with_node(
+                    Element::new("body")
+                        .with_node(Text::preserved("\n  "))
+                        .with_node(
+                            Element::new("ul")
+                                .with_node(Element::new("li").with_node("One"))
+                                .with_node(Element::new("li").with_node("Two")),
+                        )
+                        .with_node(Text::preserved(" "))
+                        .with_node(
+                            Element::new("p")
+                                .with_attr("class", "intro text")
+                                .with_attr("title", "")
+                                .with_node("Hello")
+                                .with_node(Text::preserved(" "))
+                                .with_node(Element::new("em").with_node("world")),
+                        )
+                        .with_node(
+                            Element::new("div").with_node(
+                                Element::new("input")
+                                    .with_attr("type", "checkbox")
+                                    .with_attr("checked", true),
+                            ),
+                        )
+                        .with_node(Element::new("pre").with_node(Text::preserved(" "))),
+                ),
+        );
+
+        assert_eq!(
+            render_with(&document, &RendererConfig::minify()).unwrap(),
+            "<!DOCTYPE html><html lang=en><head><title>Hi</title><body>\n  <ul><li>One<li>Two</ul>\
+             <p class=\"intro text\" title>Hello <em>world</em><div><input type=checkbox checked></div>\
+             <pre> </pre>"
+        );
+
+        let element = Element::new("a").with_node(Element::new("p").with_node("link"));
+
+        assert_eq!(
+            render_with(&element, &RendererConfig::minify()).unwrap(),
+            "<a><p>link</p></a>"
+        );
+
+        let element = Element::new("div")
+            .with_node(Element::new("p").with_node("one"))
+            .with_node("two");
+
+        assert_eq!(
+            render_with(&element, &RendererConfig::minify()).unwrap(),
+            "<div><p>one</p>two</div>"
+        );
+    }
+
+    #[test]
+    fn test_render_xml() {
+        let document = Document::new().with_node(
+            Element::new("html")
+                .with_namespace(namespace::HTML)
+                .with_node(
+                    Element::new("body")
+                        .with_node(Instruction::new("php", "echo 1;"))
+                        .with_node(Element::new("input").with_attr("checked", true))
+                        .with_node(Element::new("div"))
+                        .with_node(Element::new("script").with_node(Text::preserved("a < b")))
+                        .with_node(
+                            Element::new("svg:svg")
+                                .with_prefix("svg", namespace::SVG)
+                                .with_node(Element::new("svg:rect")),
+                        ),
+                ),
+        );
+
+        assert_eq!(
+            render_with(&document, &RendererConfig::xml()).unwrap(),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+             <html xmlns=\"http://www.w3.org/1999/xhtml\"><body><?php echo 1;?>\
+             <input checked=\"checked\" /><div /><script>a &lt; b</script>\
+             <svg:svg xmlns:svg=\"http://www.w3.org/2000/svg\"><svg:rect /></svg:svg>\
+             </body></html>"
+        );
+
+        let config = RendererConfig::pretty()
+            .syntax(Syntax::Xml)
+            .declaration(false);
+
+        assert_eq!(
+            render_with(&document, &config).unwrap(),
+            "<html xmlns=\"http://www.w3.org/1999/xhtml\">
+  <body>
+    <?php echo 1;?><input checked=\"checked\" />
+    <div />
+    <script>a &lt; b</script>
+    <svg:svg xmlns:svg=\"http://www.w3.org/2000/svg\">
+      <svg:rect />
+    </svg:svg>
+  </body>
+</html>"
+        );
+    }
+
+    #[test]
+    fn test_render_limits() {
+        let element = Element::new("div").with_node(
+            Element::new("div").with_node(Element::new("div").with_node(Element::new("div"))),
+        );
+
+        match render_with(&element, &RendererConfig::default().max_depth(3)) {
+            Err(Error::DepthLimit { limit, path }) => {
+                assert_eq!(limit, 3);
+                assert_eq!(path, vec![0, 0, 0]);
+            }
+            _ => panic!("expected depth limit error"),
+        }
+
+        assert!(render_with(&element, &RendererConfig::default().max_depth(4)).is_ok());
+
+        let element = Element::new("ul")
+            .with_nodes((0..5).map(|i| Element::new("li").with_node(i.to_string()).into()));
+
+        match render_with(&element, &RendererConfig::default().max_nodes(3)) {
+            Err(Error::NodeLimit { limit, path }) => {
+                assert_eq!(limit, 3);
+                assert_eq!(path, vec![1]);
+            }
+            _ => panic!("expected node limit error"),
+        }
+
+        let element = Element::new("div")
+            .with_node(Element::new("p").with_node("one"))
+            .with_node(Element::new("p").with_node("Hello world"));
+
+        match render_with(&element, &RendererConfig::default().max_output(20)) {
+            Err(error @ Error::OutputLimit { .. }) => {
+                assert_eq!(
+                    error.to_string(),
+                    "Output limit of 20 bytes exceeded at /1/0"
+                );
+            }
+            _ => panic!("expected output limit error"),
+        }
+
+        assert_eq!(
+            render_with(&element, &RendererConfig::default().max_output(40)).unwrap(),
+            "<div><p>one</p><p>Hello world</p></div>"
+        );
+    }
+
+    #[test]
+    fn test_render_deep() {
+        let mut element = Element::new("span");
+
+        for _ in 0..100_000 {
+            element = Element::new("div").with_node(element);
+        }
+
+        let output = render(&element).unwrap();
+
+        assert!(output.starts_with("<div><div>"));
+        assert!(output.ends_with("</div></div>"));
+        assert_eq!(output.len(), 100_000 * 11 + 13);
+        assert!(element.size_hint() >= output.len());
+
+        match render_with(&element, &RendererConfig::default().max_depth(512)) {
+            Err(Error::DepthLimit { path, .. }) => assert_eq!(path.len(), 512),
+            _ => panic!("expected depth limit error"),
+        }
+    }
+
+    struct FailingWriter;
+
+    impl Write for FailingWriter {
+        fn write(&mut self, _: &[u8]) -> IoResult<usize> {
+            Err(IoError::new(ErrorKind::BrokenPipe, "broken pipe"))
+        }
+
+        fn flush(&mut self) -> IoResult<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_render_to() {
+        let element = Element::new("p").with_node("Hello & world");
+        let mut buffer = Vec::new();
+
+        render_to(&element, &mut buffer).unwrap();
+
+        assert_eq!(buffer, b"<p>Hello &amp; world</p>");
+
+        match render_to(&element, FailingWriter) {
+            Err(Error::Io(error)) => assert_eq!(error.kind(), ErrorKind::BrokenPipe),
+            _ => panic!("expected io error"),
+        }
+    }
+
+    #[test]
+    fn test_render_bytes() {
+        let document = Document::new().with_node(
+            Element::new("html").with_node(
+                Element::new("body")
+                    .with_attr("class", "page")
+                    .with_node(Element::new("p").with_node("Hello world")),
+            ),
+        );
+        let bytes = render_bytes(&document).unwrap();
+
+        assert_eq!(bytes, render(&document).unwrap().as_bytes());
+        assert!(document.size_hint() >= bytes.len());
+    }
 }
@@ -0,0 +1,228 @@
+use brace_web_core::http::header::{HeaderName, HeaderValue};
+use brace_web_core::http::{Cookie, StatusCode};
+use brace_web_core::{HttpRequest, HttpResponse, Responder};
+use futures::future::{self, Ready};
+
+use crate::util::cache::CachePolicy;
+use crate::util::context::RenderContext;
+use crate::util::render::{Error, Render};
+use crate::util::stream::{render_body, respond_body};
+
+pub struct Html<T> {
+    item: T,
+    status: StatusCode,
+    content_type: String,
+    headers: Vec<(String, String)>,
+    cookies: Vec<Cookie<'static>>,
+    policy: CachePolicy,
+}
+
+impl<T> Html<T>
+where
+    T: Render,
+{
+    pub fn new(item: T) -> Self {
+        Self {
+            item,
+            status: StatusCode::OK,
+            content_type: "text/html; charset=utf-8".to_owned(),
+            headers: Vec::new(),
+            cookies: Vec::new(),
+            policy: CachePolicy::default(),
+        }
+    }
+
+    pub fn item(&self) -> &T {
+        &self.item
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn with_status(mut self, status: StatusCode) -> Self {
+        self.status = status;
+        self
+    }
+
+    pub fn content_type(&self) -> &str {
+        &self.content_type
+    }
+
+    pub fn with_content_type<C>(mut self, content_type: C) -> Self
+    where
+        C: Into<String>,
+    {
+        self.content_type = content_type.into();
+        self
+    }
+
+    pub fn with_header<N, V>(mut self, name: N, value: V) -> Self
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn with_cookie(mut self, cookie: Cookie<'static>) -> Self {
+        self.cookies.push(cookie);
+        self
+    }
+
+    pub fn with_policy(mut self, policy: CachePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn into_inner(self) -> T {
+        self.item
+    }
+
+    pub fn into_response(self, req: &HttpRequest) -> Result<HttpResponse, Error> {
+        let headers = self
+            .headers
+            .iter()
+            .map(|(name, value)| parse_header(name, value))
+            .collect::<Result<Vec<_>, _>>()?;
+        let context = RenderContext::from_request(req);
+        let body = render_body(&self.item, &context)?;
+        let content_type = self.content_type;
+        let cookies = self.cookies;
+
+        Ok(respond_body(
+            req,
+            self.status,
+            &self.policy,
+            &content_type,
+            body,
+            |builder| {
+                for (name, value) in headers {
+                    builder.header(name, value);
+                }
+
+                for cookie in cookies {
+                    builder.cookie(cookie);
+                }
+            },
+        ))
+    }
+}
+
+impl<T> Responder for Html<T>
+where
+    T: Render,
+{
+    type Error = Error;
+    type Future = Ready<Result<HttpResponse, Self::Error>>;
+
+    fn respond_to(self, req: &HttpRequest) -> Self::Future {
+        future::ready(self.into_response(req))
+    }
+}
+
+impl<T> From<T> for Html<T>
+where
+    T: Render,
+{
+    fn from(from: T) -> Self {
+        Self::new(from)
+    }
+}
+
+fn parse_header(name: &str, value: &str) -> Result<(HeaderName, HeaderValue), Error> {
+    match (
+        HeaderName::from_bytes(name.as_bytes()),
+        HeaderValue::from_str(value),
+    ) {
+        (Ok(name), Ok(value)) => Ok((name, value)),
+        _ => Err(Error::InvalidHeader(name.to_owned())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use brace_web_core::http::header::{CONTENT_TYPE, ETAG, IF_NONE_MATCH, SET_COOKIE, VARY};
+    use brace_web_core::http::{Cookie, StatusCode};
+    use brace_web_core::test::TestRequest;
+    use brace_web_core::Responder;
+
+    use super::Html;
+    use crate::util::render::Error;
+    use crate::{Document, Element};
+
+    #[actix_rt::test]
+    async fn test_html_response() {
+        let req = TestRequest::default().to_http_request();
+        let res = Html::new(Document::new().with_node(Element::new("h1").with_node("Not found")))
+            .with_status(StatusCode::NOT_FOUND)
+            .with_header("x-page", "missing")
+            .with_cookie(Cookie::new("session", "abc"))
+            .respond_to(&req)
+            .await
+            .unwrap();
+
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            res.headers().get(CONTENT_TYPE).unwrap(),
+            "text/html; charset=utf-8"
+        );
+        assert_eq!(res.headers().get("x-page").unwrap(), "missing");
+        assert_eq!(res.headers().get(SET_COOKIE).unwrap(), "session=abc");
+
+        let etag = res.headers().get(ETAG).unwrap().clone();
+        let req = TestRequest::default()
+            .header(IF_NONE_MATCH, etag)
+            .to_http_request();
+        let res = Html::new(Document::new().with_node(Element::new("h1").with_node("Not found")))
+            .with_status(StatusCode::NOT_FOUND)
+            .respond_to(&req)
+            .await
+            .unwrap();
+
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+        let res = Html::new(Element::new("p"))
+            .with_content_type("application/xhtml+xml")
+            .respond_to(&req)
+            .await
+            .unwrap();
+
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(
+            res.headers().get(CONTENT_TYPE).unwrap(),
+            "application/xhtml+xml"
+        );
+
+        match Html::new(Element::new("p"))
+            .with_header("bad header", "value")
+            .respond_to(&req)
+            .await
+        {
+            Err(Error::InvalidHeader(name)) => assert_eq!(name, "bad header"),
+            _ => panic!("expected invalid header error"),
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_html_not_modified() {
+        let html = || {
+            Html::new(Element::new("p").with_node("Hello"))
+                .with_header("vary", "Accept-Language")
+                .with_cookie(Cookie::new("session", "abc"))
+        };
+        let req = TestRequest::default().to_http_request();
+        let res = html().respond_to(&req).await.unwrap();
+        let etag = res.headers().get(ETAG).unwrap().clone();
+        let req = TestRequest::default()
+            .header(IF_NONE_MATCH, etag)
+            .to_http_request();
+        let res = html().respond_to(&req).await.unwrap();
+
+        assert_eq!(res.status(), StatusCode::NOT_MODIFIED);
+        assert!(res.headers().get(CONTENT_TYPE).is_none());
+        assert_eq!(res.headers().get(VARY).unwrap(), "Accept-Language");
+        assert_eq!(res.headers().get(SET_COOKIE).unwrap(), "session=abc");
+    }
+}
@@ -0,0 +1,794 @@
+use std::borrow::Cow;
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+
+use crate::{Attribute, Element, Node, Nodes};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Selector(Vec<Complex>);
+
+impl Selector {
+    pub fn parse(input: &str) -> Result<Self, Error> {
+        let mut parser = Parser { input, position: 0 };
+        let selector = parser.selector()?;
+
+        parser.whitespace();
+
+        match parser.peek() {
+            None => Ok(selector),
+            Some(_) => Err(parser.error("unexpected character")),
+        }
+    }
+
+    pub fn is_match(&self, element: &Element) -> bool {
+        self.matches(
+            &[],
+            Level {
+                element,
+                siblings: None,
+                index: 0,
+            },
+        )
+    }
+
+    pub fn select<'a>(&self, nodes: &'a Nodes) -> Select<'a, '_> {
+        Select {
+            selector: self,
+            positions: vec![(nodes, 0)],
+            levels: Vec::new(),
+        }
+    }
+
+    pub fn select_paths(&self, nodes: &Nodes) -> Vec<Vec<usize>> {
+        let mut select = self.select(nodes);
+        let mut paths = Vec::new();
+
+        while select.next().is_some() {
+            paths.push(select.path());
+        }
+
+        paths
+    }
+
+    fn matches(&self, ancestors: &[Level], level: Level) -> bool {
+        self.0
+            .iter()
+            .any(|complex| complex.matches(complex.0.len() - 1, ancestors, level))
+    }
+}
+
+impl FromStr for Selector {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Self::parse(input)
+    }
+}
+
+pub struct Select<'a, 's> {
+    selector: &'s Selector,
+    positions: Vec<(&'a Nodes, usize)>,
+    levels: Vec<Level<'a>>,
+}
+
+impl<'a, 's> Select<'a, 's> {
+    pub fn path(&self) -> Vec<usize> {
+        self.positions
+            .iter()
+            .take(self.positions.len() - 1)
+            .map(|(_, index)| *index)
+            .collect()
+    }
+}
+
+impl<'a, 's> Iterator for Select<'a, 's> {
+    type Item = &'a Element;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (nodes, index) = *self.positions.last()?;
+
+            match nodes.get(index) {
+                Some(Node::Element(element)) => {
+                    let level = Level {
+                        element,
+                        siblings: Some(nodes),
+                        index,
+                    };
+                    let matched = self.selector.matches(&self.levels, level);
+
+                    self.levels.push(level);
+                    self.positions.push((element.nodes(), 0));
+
+                    if matched {
+                        return Some(element);
+                    }
+                }
+                Some(_) => self.positions.last_mut()?.1 += 1,
+                None => {
+                    self.positions.pop();
+                    self.levels.pop();
+
+                    if let Some((_, index)) = self.positions.last_mut() {
+                        *index += 1;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Error {
+    position: usize,
+    message: &'static str,
+}
+
+impl Error {
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(
+            f,
+            "Invalid selector at position {}: {}",
+            self.position, self.message
+        )
+    }
+}
+
+impl StdError for Error {}
+
+#[derive(Clone, Copy)]
+struct Level<'a> {
+    element: &'a Element,
+    siblings: Option<&'a Nodes>,
+    index: usize,
+}
+
+impl<'a> Level<'a> {
+    fn previous(&self) -> impl Iterator<Item = Level<'a>> {
+        let siblings = self.siblings;
+        let index = self.index;
+
+        (0..index).rev().filter_map(move |index| {
+            let element = siblings?.get(index)?.as_element()?;
+
+            Some(Level {
+                element,
+                siblings,
+                index,
+            })
+        })
+    }
+
+    fn position(&self) -> (usize, usize) {
+        let siblings = match self.siblings {
+            Some(siblings) => siblings,
+            None => return (1, 1),
+        };
+        let mut before = 0;
+        let mut count = 0;
+
+        for (index, node) in siblings.iter().enumerate() {
+            if node.is_element() {
+                count += 1;
+
+                if index < self.index {
+                    before += 1;
+                }
+            }
+        }
+
+        (before + 1, count)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Complex(Vec<(Combinator, Compound)>);
+
+impl Complex {
+    fn matches(&self, index: usize, ancestors: &[Level], level: Level) -> bool {
+        let (combinator, compound) = &self.0[index];
+
+        if !compound.matches(ancestors, level) {
+            return false;
+        }
+
+        if index == 0 {
+            return true;
+        }
+
+        match combinator {
+            Combinator::Descendant => (0..ancestors.len())
+                .rev()
+                .any(|parent| self.matches(index - 1, &ancestors[..parent], ancestors[parent])),
+            Combinator::Child => match ancestors.split_last() {
+                Some((parent, ancestors)) => self.matches(index - 1, ancestors, *parent),
+                None => false,
+            },
+            Combinator::Adjacent => level
+                .previous()
+                .next()
+                .map_or(false, |sibling| self.matches(index - 1, ancestors, sibling)),
+            Combinator::Sibling => level
+                .previous()
+                .any(|sibling| self.matches(index - 1, ancestors, sibling)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Combinator {
+    Descendant,
+    Child,
+    Adjacent,
+    Sibling,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Compound {
+    tag: Option<String>,
+    simple: Vec<Simple>,
+}
+
+impl Compound {
+    fn matches(&self, ancestors: &[Level], level: Level) -> bool {
+        let element = level.element;
+
+        if let Some(tag) = &self.tag {
+            if !tag.eq_ignore_ascii_case(element.tag()) {
+                return false;
+            }
+        }
+
+        self.simple.iter().all(|simple| match simple {
+            Simple::Id(id) => value(element, "id").map_or(false, |value| value == *id),
+            Simple::Class(class) => value(element, "class").map_or(false, |value| {
+                value.split_whitespace().any(|item| item == class)
+            }),
+            Simple::Attribute(name, matcher) => match (value(element, name), matcher) {
+                (Some(value), Some(matcher)) => matcher.matches(&value),
+                (Some(_), None) => true,
+                (None, _) => false,
+            },
+            Simple::Nth(nth, last) => {
+                let (position, count) = level.position();
+
+                if *last {
+                    nth.matches(count + 1 - position)
+                } else {
+                    nth.matches(position)
+                }
+            }
+            Simple::Only => level.position() == (1, 1),
+            Simple::Not(selector) => !selector.matches(ancestors, level),
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Simple {
+    Id(String),
+    Class(String),
+    Attribute(String, Option<Matcher>),
+    Nth(Nth, bool),
+    Only,
+    Not(Selector),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Matcher {
+    operator: Operator,
+    value: String,
+    insensitive: bool,
+}
+
+impl Matcher {
+    fn matches(&self, value: &str) -> bool {
+        let (value, expected) = if self.insensitive {
+            (value.to_lowercase(), self.value.to_lowercase())
+        } else {
+            (value.to_owned(), self.value.clone())
+        };
+
+        match self.operator {
+            Operator::Equals => value == expected,
+            Operator::Includes => value.split_whitespace().any(|item| item == expected),
+            Operator::Dash => value == expected || value.starts_with(&format!("{}-", expected)),
+            Operator::Prefix => !expected.is_empty() && value.starts_with(&expected),
+            Operator::Suffix => !expected.is_empty() && value.ends_with(&expected),
+            Operator::Substring => !expected.is_empty() && value.contains(&expected),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Operator {
+    Equals,
+    Includes,
+    Dash,
+    Prefix,
+    Suffix,
+    Substring,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Nth(isize, isize);
+
+impl Nth {
+    fn matches(self, position: usize) -> bool {
+        let Nth(step, offset) = self;
+        let delta = position as isize - offset;
+
+        if step == 0 {
+            delta == 0
+        } else {
+            delta % step == 0 && delta / step >= 0
+        }
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn selector(&mut self) -> Result<Selector, Error> {
+        let mut list = vec![self.complex()?];
+
+        loop {
+            self.whitespace();
+
+            if !self.eat(',') {
+                return Ok(Selector(list));
+            }
+
+            list.push(self.complex()?);
+        }
+    }
+
+    fn complex(&mut self) -> Result<Complex, Error> {
+        self.whitespace();
+
+        let mut parts = vec![(Combinator::Descendant, self.compound()?)];
+
+        loop {
+            let whitespace = self.whitespace();
+            let combinator = match self.peek() {
+                Some('>') => Combinator::Child,
+                Some('+') => Combinator::Adjacent,
+                Some('~') => Combinator::Sibling,
+                Some(',') | Some(')') | None => return Ok(Complex(parts)),
+                Some(_) if whitespace => {
+                    parts.push((Combinator::Descendant, self.compound()?));
+                    continue;
+                }
+                Some(_) => return Err(self.error("expected combinator")),
+            };
+
+            self.bump();
+            self.whitespace();
+            parts.push((combinator, self.compound()?));
+        }
+    }
+
+    fn compound(&mut self) -> Result<Compound, Error> {
+        let mut compound = Compound::default();
+
+        if self.eat('*') {
+            compound.tag = None;
+        } else if self.peek().map_or(false, is_ident) {
+            compound.tag = Some(self.ident()?);
+        } else if !self.peek().map_or(false, |c| "#.[:".contains(c)) {
+            return Err(self.error("expected selector"));
+        }
+
+        loop {
+            let simple = match self.peek() {
+                Some('#') => {
+                    self.bump();
+                    Simple::Id(self.ident()?)
+                }
+                Some('.') => {
+                    self.bump();
+                    Simple::Class(self.ident()?)
+                }
+                Some('[') => {
+                    self.bump();
+                    self.attribute()?
+                }
+                Some(':') => {
+                    self.bump();
+                    self.pseudo()?
+                }
+                _ => return Ok(compound),
+            };
+
+            compound.simple.push(simple);
+        }
+    }
+
+    fn attribute(&mut self) -> Result<Simple, Error> {
+        self.whitespace();
+
+        let name = self.ident()?;
+
+        self.whitespace();
+
+        let operator = match self.peek() {
+            Some(']') => {
+                self.bump();
+                return Ok(Simple::Attribute(name, None));
+            }
+            Some('=') => Operator::Equals,
+            Some('~') => Operator::Includes,
+            Some('|') => Operator::Dash,
+            Some('^') => Operator::Prefix,
+            Some('$') => Operator::Suffix,
+            Some('*') => Operator::Substring,
+            _ => return Err(self.error("expected attribute operator")),
+        };
+
+        self.bump();
+
+        if operator != Operator::Equals && !self.eat('=') {
+            return Err(self.error("expected '='"));
+        }
+
+        self.whitespace();
+
+        let value = match self.peek() {
+            Some(quote) if quote == '"' || quote == '\'' => self.string(quote)?,
+            _ => self.ident()?,
+        };
+
+        self.whitespace();
+
+        let insensitive = self.eat('i') || self.eat('I');
+
+        self.whitespace();
+
+        if !self.eat(']') {
+            return Err(self.error("expected ']'"));
+        }
+
+        Ok(Simple::Attribute(
+            name,
+            Some(Matcher {
+                operator,
+                value,
+                insensitive,
+            }),
+        ))
+    }
+
+    fn pseudo(&mut self) -> Result<Simple, Error> {
+        let position = self.position;
+        let name = self.ident()?.to_ascii_lowercase();
+
+        match name.as_str() {
+            "first-child" => Ok(Simple::Nth(Nth(0, 1), false)),
+            "last-child" => Ok(Simple::Nth(Nth(0, 1), true)),
+            "only-child" => Ok(Simple::Only),
+            "nth-child" | "nth-last-child" => {
+                let nth = self.arguments(Self::nth)?;
+
+                Ok(Simple::Nth(nth, name == "nth-last-child"))
+            }
+            "not" => Ok(Simple::Not(self.arguments(Self::selector)?)),
+            _ => Err(Error {
+                position,
+                message: "unsupported pseudo-class",
+            }),
+        }
+    }
+
+    fn arguments<T, F>(&mut self, f: F) -> Result<T, Error>
+    where
+        F: FnOnce(&mut Self) -> Result<T, Error>,
+    {
+        if !self.eat('(') {
+            return Err(self.error("expected '('"));
+        }
+
+        self.whitespace();
+
+        let value = f(self)?;
+
+        self.whitespace();
+
+        if !self.eat(')') {
+            return Err(self.error("expected ')'"));
+        }
+
+        Ok(value)
+    }
+
+    fn nth(&mut self) -> Result<Nth, Error> {
+        let start = self.position;
+
+        while self
+            .peek()
+            .map_or(false, |c| c.is_ascii_alphanumeric() || "+- ".contains(c))
+        {
+            self.bump();
+        }
+
+        let value = self.input[start..self.position]
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+            .to_ascii_lowercase();
+        let error = Error {
+            position: start,
+            message: "invalid nth expression",
+        };
+
+        match value.as_str() {
+            "odd" => return Ok(Nth(2, 1)),
+            "even" => return Ok(Nth(2, 0)),
+            _ => (),
+        }
+
+        let (step, offset) = match value.find('n') {
+            Some(index) => (&value[..index], &value[index + 1..]),
+            None => {
+                return value
+                    .parse()
+                    .map(|offset| Nth(0, offset))
+                    .map_err(|_| error)
+            }
+        };
+        let step = match step {
+            "" | "+" => 1,
+            "-" => -1,
+            step => step.parse().map_err(|_| error.clone())?,
+        };
+        let offset = match offset {
+            "" => 0,
+            offset if offset.starts_with('+') => offset[1..].parse().map_err(|_| error)?,
+            offset if offset.starts_with('-') => offset.parse().map_err(|_| error)?,
+            _ => return Err(error),
+        };
+
+        Ok(Nth(step, offset))
+    }
+
+    fn ident(&mut self) -> Result<String, Error> {
+        let start = self.position;
+
+        while self.peek().map_or(false, is_ident) {
+            self.bump();
+        }
+
+        if start == self.position {
+            return Err(self.error("expected identifier"));
+        }
+
+        Ok(self.input[start..self.position].to_owned())
+    }
+
+    fn string(&mut self, quote: char) -> Result<String, Error> {
+        let mut value = String::new();
+
+        self.bump();
+
+        loop {
+            match self.peek() {
+                Some('\\') => {
+                    self.bump();
+
+                    if let Some(c) = self.peek() {
+                        value.push(c);
+                        self.bump();
+                    }
+                }
+                Some(c) if c == quote => {
+                    self.bump();
+                    return Ok(value);
+                }
+                Some(c) => {
+                    value.push(c);
+                    self.bump();
+                }
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+
+    fn whitespace(&mut self) -> bool {
+        let start = self.position;
+
+        while self.peek().map_or(false, char::is_whitespace) {
+            self.bump();
+        }
+
+        start != self.position
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.position..].chars().next()
+    }
+
+    fn bump(&mut self) {
+        if let Some(c) = self.peek() {
+            self.position += c.len_utf8();
+        }
+    }
+
+    fn error(&self, message: &'static str) -> Error {
+        Error {
+            position: self.position,
+            message,
+        }
+    }
+}
+
+fn is_ident(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_' || !c.is_ascii()
+}
+
+fn value<'a>(element: &'a Element, name: &str) -> Option<Cow<'a, str>> {
+    match element.get_attr(name)? {
+        Attribute::Boolean(true) => Some(Cow::Borrowed("")),
+        attr => attr.to_value(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Selector;
+    use crate::{Document, Element, Node};
+
+    fn document() -> Document {
+        Document::new().with_node(
+            Element::new("html").with_node(
+                Element::new("body")
+                    .with_node(
+                        Element::new("nav").with_node(
+                            Element::new("ul")
+                                .with_node(Element::new("li").with_node(
+                                    Element::new("a").with_attr("href", "https://example.com"),
+                                ))
+                                .with_node(Node::comment("separator"))
+                                .with_node(
+                                    Element::new("li")
+                                        .with_attr("class", "active item")
+                                        .with_node(Element::new("a").with_attr("href", "/about")),
+                                )
+                                .with_node(
+                                    Element::new("li")
+                                        .with_attr("lang", "en-GB")
+                                        .with_node(Element::new("a").with_attr("href", "/blog")),
+                                ),
+                        ),
+                    )
+                    .with_node(
+                        Element::new("main")
+                            .with_attr("id", "content")
+                            .with_node(Element::new("h1").with_node("Title"))
+                            .with_node(Element::new("p").with_attr("hidden", true))
+                            .with_node(Element::new("p").with_attr("hidden", false)),
+                    ),
+            ),
+        )
+    }
+
+    fn select(selector: &str) -> Vec<String> {
+        document()
+            .select(selector)
+            .unwrap()
+            .into_iter()
+            .map(|element| {
+                let href = element
+                    .get_attr("href")
+                    .and_then(|href| href.as_string())
+                    .map_or(String::new(), |href| format!("={}", href));
+
+                format!("{}{}", element.tag(), href)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_selector_simple() {
+        assert_eq!(select("li").len(), 3);
+        assert_eq!(select("*").len(), 14);
+        assert_eq!(select("#content"), vec!["main"]);
+        assert_eq!(select("li.active.item > a"), vec!["a=/about"]);
+        assert_eq!(select("a[href^=https]"), vec!["a=https://example.com"]);
+        assert_eq!(select("a[href$=\"out\"]"), vec!["a=/about"]);
+        assert_eq!(select("a[href*='blo']"), vec!["a=/blog"]);
+        assert_eq!(select("li[lang|=en] a"), vec!["a=/blog"]);
+        assert_eq!(select("li[class~=item]"), vec!["li"]);
+        assert_eq!(select("[href='/ABOUT']"), Vec::<String>::new());
+        assert_eq!(select("[href='/ABOUT' i]"), vec!["a=/about"]);
+        assert_eq!(select("p[hidden]").len(), 1);
+    }
+
+    #[test]
+    fn test_selector_combinators() {
+        assert_eq!(select("nav a").len(), 3);
+        assert_eq!(select("body > a").len(), 0);
+        assert_eq!(select("nav > ul > li > a").len(), 3);
+        assert_eq!(select("h1 + p").len(), 1);
+        assert_eq!(select("h1 ~ p").len(), 2);
+        assert_eq!(select("li + li > a"), vec!["a=/about", "a=/blog"]);
+        assert_eq!(select("nav, main").len(), 2);
+    }
+
+    #[test]
+    fn test_selector_pseudo() {
+        assert_eq!(select("li:first-child a"), vec!["a=https://example.com"]);
+        assert_eq!(select("li:last-child a"), vec!["a=/blog"]);
+        assert_eq!(select("li:nth-child(2) a"), vec!["a=/about"]);
+        assert_eq!(select("li:nth-child(odd) a").len(), 2);
+        assert_eq!(select("li:nth-child(-n + 2)").len(), 2);
+        assert_eq!(select("li:nth-last-child(1) a"), vec!["a=/blog"]);
+        assert_eq!(select("a:only-child").len(), 3);
+        assert_eq!(
+            select("li:not(.active, [lang]) a"),
+            vec!["a=https://example.com"]
+        );
+        assert_eq!(select("main :not(h1)").len(), 2);
+    }
+
+    #[test]
+    fn test_selector_errors() {
+        assert!(Selector::parse("").is_err());
+        assert!(Selector::parse("div >").is_err());
+        assert!(Selector::parse("a[href").is_err());
+        assert!(Selector::parse("a:hover").is_err());
+        assert!(Selector::parse("li:nth-child(x)").is_err());
+        assert_eq!(Selector::parse("div )").unwrap_err().position(), 4);
+        assert!("ul > li".parse::<Selector>().is_ok());
+    }
+
+    #[test]
+    fn test_selector_mut() {
+        let mut document = document();
+        let count = document
+            .select_mut("li > a", |element| {
+                element.set_attr("class", "link");
+            })
+            .unwrap();
+
+        assert_eq!(count, 3);
+        assert_eq!(document.select("a.link").unwrap().len(), 3);
+
+        document
+            .select_first_mut("main p")
+            .unwrap()
+            .unwrap()
+            .nodes_mut()
+            .append("Hello");
+
+        assert_eq!(
+            document
+                .select_first("#content > p")
+                .unwrap()
+                .and_then(|element| element.get_node(0))
+                .and_then(Node::as_text)
+                .map(|text| text.value()),
+            Some("Hello")
+        );
+        assert!(Selector::parse("a[href]")
+            .unwrap()
+            .is_match(&Element::new("a").with_attr("href", "/")));
+    }
+}
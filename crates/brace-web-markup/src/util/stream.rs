@@ -0,0 +1,456 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use brace_web_core::dev::HttpResponseBuilder;
+use brace_web_core::http::StatusCode;
+use brace_web_core::{HttpRequest, HttpResponse};
+use bytes::{Bytes, BytesMut};
+use futures::stream::Stream;
+
+use crate::util::cache::CachePolicy;
+use crate::util::context::RenderContext;
+use crate::util::render::{
+    ascend, step, Error, Frame, Render, Renderer, RendererConfig, State, Step,
+};
+use crate::{Document, Element, Node, Nodes};
+
+const CHUNK_SIZE: usize = 8 * 1024;
+const STREAM_THRESHOLD: usize = 64 * 1024;
+
+pub(crate) fn respond<T>(item: T, req: &HttpRequest) -> Result<HttpResponse, Error>
+where
+    T: Render + Into<RenderStream>,
+{
+    respond_with(item, req, &CachePolicy::default())
+}
+
+pub(crate) fn respond_with<T>(
+    item: T,
+    req: &HttpRequest,
+    policy: &CachePolicy,
+) -> Result<HttpResponse, Error>
+where
+    T: Render + Into<RenderStream>,
+{
+    let context = RenderContext::from_request(req);
+
+    if item.size_hint() > STREAM_THRESHOLD {
+        let etag = policy.etag_for(&item, &context)?;
+
+        if policy.is_fresh(req, etag.as_deref()) {
+            return Ok(not_modified(policy, etag.as_deref()));
+        }
+
+        let mut builder = HttpResponse::Ok();
+
+        policy.apply(&mut builder, etag.as_deref());
+
+        return Ok(builder
+            .content_type("text/html; charset=utf-8")
+            .streaming(item.into().with_context(context)));
+    }
+
+    let body = render_body(&item, &context)?;
+
+    Ok(respond_body(
+        req,
+        StatusCode::OK,
+        policy,
+        "text/html; charset=utf-8",
+        body,
+        |_| (),
+    ))
+}
+
+pub(crate) fn render_body<T>(item: &T, context: &RenderContext) -> Result<BytesMut, Error>
+where
+    T: Render,
+{
+    let mut buffer = BytesMut::with_capacity(item.size_hint());
+
+    Renderer::new(&mut buffer)
+        .with_context(context)
+        .render(item)?;
+
+    Ok(buffer)
+}
+
+pub(crate) fn respond_body<F>(
+    req: &HttpRequest,
+    status: StatusCode,
+    policy: &CachePolicy,
+    content_type: &str,
+    body: BytesMut,
+    headers: F,
+) -> HttpResponse
+where
+    F: FnOnce(&mut HttpResponseBuilder),
+{
+    let etag = policy.etag_of(&body);
+    let fresh = status.is_success() && policy.is_fresh(req, etag.as_deref());
+    let mut builder = if fresh {
+        HttpResponse::NotModified()
+    } else {
+        HttpResponse::build(status)
+    };
+
+    policy.apply(&mut builder, etag.as_deref());
+    headers(&mut builder);
+
+    if fresh {
+        return builder.finish();
+    }
+
+    builder.content_type(content_type).body(body.freeze())
+}
+
+fn not_modified(policy: &CachePolicy, etag: Option<&str>) -> HttpResponse {
+    let mut builder = HttpResponse::NotModified();
+
+    policy.apply(&mut builder, etag);
+    builder.finish()
+}
+
+pub struct RenderStream {
+    root: Nodes,
+    document: Option<Document>,
+    config: RendererConfig,
+    context: RenderContext,
+    chunk_size: usize,
+    buffer: BytesMut,
+    stack: Vec<Frame>,
+    state: State,
+    started: bool,
+}
+
+impl RenderStream {
+    pub fn new<T>(nodes: T) -> Self
+    where
+        T: Into<Nodes>,
+    {
+        Self {
+            root: nodes.into(),
+            document: None,
+            config: RendererConfig::default(),
+            context: RenderContext::default(),
+            chunk_size: CHUNK_SIZE,
+            buffer: BytesMut::new(),
+            stack: Vec::new(),
+            state: State::default(),
+            started: false,
+        }
+    }
+
+    pub fn document(mut document: Document) -> Self {
+        let nodes = std::mem::take(document.nodes_mut());
+
+        Self {
+            document: Some(document),
+            ..Self::new(nodes)
+        }
+    }
+
+    pub fn with_config(mut self, config: RendererConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn with_context(mut self, context: RenderContext) -> Self {
+        self.context = context;
+        self
+    }
+
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    fn next_chunk(&mut self) -> Option<Result<Bytes, Error>> {
+        if self.started && self.stack.is_empty() {
+            return None;
+        }
+
+        let mut buffer = std::mem::take(&mut self.buffer);
+        let result = self.fill(&mut buffer);
+
+        self.buffer = buffer;
+
+        if let Err(error) = result {
+            self.started = true;
+            self.stack.clear();
+
+            return Some(Err(error));
+        }
+
+        if self.buffer.is_empty() && self.stack.is_empty() {
+            return None;
+        }
+
+        Some(Ok(self.buffer.split().freeze()))
+    }
+
+    fn fill(&mut self, buffer: &mut BytesMut) -> Result<(), Error> {
+        let mut renderer = Renderer::with_config(buffer, &self.config).with_context(&self.context);
+
+        renderer.state = self.state;
+
+        let start = renderer.written();
+
+        if !self.started {
+            self.started = true;
+
+            if let Some(document) = &self.document {
+                document.render_doctype(&mut renderer)?;
+            }
+
+            let leading = self
+                .document
+                .as_ref()
+                .map_or(false, |document| document.has_prolog(&self.config));
+
+            self.stack.push(Frame::root(&renderer, leading));
+        }
+
+        let mut elements = resolve(&self.root, &self.stack);
+
+        while !self.stack.is_empty() && renderer.written() - start < self.chunk_size {
+            let parent = elements.last().copied();
+            let nodes = parent.map_or(&self.root, Element::nodes);
+
+            match step(nodes, parent, &mut self.stack, &mut renderer)? {
+                Step::Enter(element) => elements.push(element),
+                Step::Next => (),
+                Step::Leave => match elements.pop() {
+                    Some(element) => ascend(element, &mut self.stack, &mut renderer)?,
+                    None => self.stack.clear(),
+                },
+            }
+        }
+
+        self.state = renderer.state;
+
+        Ok(())
+    }
+}
+
+fn resolve<'a>(root: &'a Nodes, stack: &[Frame]) -> Vec<&'a Element> {
+    let mut nodes = root;
+    let mut elements = Vec::new();
+
+    for frame in stack.iter().take(stack.len().saturating_sub(1)) {
+        let element = match nodes.get(frame.index()).and_then(Node::as_element) {
+            Some(element) => element,
+            None => break,
+        };
+
+        nodes = element.nodes();
+        elements.push(element);
+    }
+
+    elements
+}
+
+impl Stream for RenderStream {
+    type Item = Result<Bytes, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, _: &mut Context) -> Poll<Option<Self::Item>> {
+        Poll::Ready(self.get_mut().next_chunk())
+    }
+}
+
+impl From<Document> for RenderStream {
+    fn from(from: Document) -> Self {
+        Self::document(from)
+    }
+}
+
+impl From<Element> for RenderStream {
+    fn from(from: Element) -> Self {
+        Self::new(from)
+    }
+}
+
+impl From<Node> for RenderStream {
+    fn from(from: Node) -> Self {
+        Self::new(from)
+    }
+}
+
+impl From<Nodes> for RenderStream {
+    fn from(from: Nodes) -> Self {
+        Self::new(from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use brace_web_core::dev::{BodySize, MessageBody};
+    use brace_web_core::test::TestRequest;
+    use brace_web_core::Responder;
+    use bytes::Bytes;
+    use futures::executor::block_on;
+    use futures::stream::StreamExt;
+
+    use super::RenderStream;
+    use crate::util::render::{render, render_with, Error, RendererConfig};
+    use crate::{Document, Element, Node, Nodes, Text};
+
+    fn collect(stream: RenderStream) -> Result<String, Error> {
+        let chunks: Vec<Result<Bytes, Error>> = block_on(stream.collect());
+        let mut body = Vec::new();
+
+        for chunk in chunks {
+            body.extend_from_slice(&chunk?);
+        }
+
+        Ok(String::from_utf8(body).unwrap())
+    }
+
+    fn document() -> Document {
+        Document::new().with_node(Element::new("html").with_nodes(vec![
+                Element::new("head")
+                    .with_node(Element::new("title").with_node("Hello & welcome"))
+                    .into(),
+                Element::new("body")
+                    .with_nodes(vec![
+                        Element::new("p")
+                            .with_node("Hello")
+                            .with_node(Element::new("em").with_node("world"))
+                            .into(),
+                        Node::comment("content"),
+                        Element::new("script")
+                            .with_node(Text::preserved("if (a < b) { '</script>'; }"))
+                            .into(),
+                        Element::new("ul")
+                            .with_nodes((0..50).map(|i| {
+                                Element::new("li")
+                                    .with_attr("data-index", i.to_string())
+                                    .with_node(Text::new(format!("Item {}", i)))
+                                    .into()
+                            }))
+                            .into(),
+                        Element::new("hr").into(),
+                        Element::new("div").into(),
+                    ])
+                    .into(),
+            ]))
+    }
+
+    #[test]
+    fn test_stream_document() {
+        let document = document();
+        let expected = render(&document).unwrap();
+
+        for chunk_size in &[1, 7, 64, 1024, 64 * 1024] {
+            let stream = RenderStream::from(document.clone()).with_chunk_size(*chunk_size);
+
+            assert_eq!(collect(stream).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_stream_pretty() {
+        let document = document();
+        let config = RendererConfig::pretty();
+        let expected = render_with(&document, &config).unwrap();
+
+        for chunk_size in &[1, 13, 1024] {
+            let stream = RenderStream::from(document.clone())
+                .with_config(config.clone())
+                .with_chunk_size(*chunk_size);
+
+            assert_eq!(collect(stream).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_stream_minify() {
+        let document = document();
+        let config = RendererConfig::minify();
+        let expected = render_with(&document, &config).unwrap();
+
+        for chunk_size in &[1, 13, 1024] {
+            let stream = RenderStream::from(document.clone())
+                .with_config(config.clone())
+                .with_chunk_size(*chunk_size);
+
+            assert_eq!(collect(stream).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_stream_deep() {
+        let mut element = Element::new("span");
+
+        for _ in 0..20_000 {
+            element = Element::new("div").with_node(element);
+        }
+
+        let expected = render(&element).unwrap();
+        let stream = RenderStream::from(element).with_chunk_size(1024);
+
+        assert_eq!(collect(stream).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_stream_chunks() {
+        let stream = RenderStream::from(document()).with_chunk_size(64);
+        let chunks: Vec<Result<Bytes, Error>> = block_on(stream.collect());
+
+        assert!(chunks.len() > 10);
+
+        for chunk in chunks {
+            assert!(chunk.unwrap().len() < 64 * 2);
+        }
+    }
+
+    #[test]
+    fn test_stream_nodes() {
+        let nodes = Nodes::from(vec![
+            Element::new("p").with_node("one"),
+            Element::new("p").with_node("two"),
+        ]);
+
+        assert_eq!(
+            collect(RenderStream::from(nodes.clone())).unwrap(),
+            render(&nodes).unwrap()
+        );
+        assert_eq!(collect(RenderStream::new(Nodes::new())).unwrap(), "");
+        assert_eq!(
+            collect(RenderStream::from(Document::new())).unwrap(),
+            "<!DOCTYPE html>"
+        );
+    }
+
+    #[test]
+    fn test_stream_error() {
+        let element = Element::new("div")
+            .with_node(Element::new("p").with_node("one"))
+            .with_node(Element::new("bad tag"));
+
+        match collect(RenderStream::from(element).with_chunk_size(1)) {
+            Err(Error::InvalidTag(tag)) => assert_eq!(tag, "bad tag"),
+            _ => panic!("expected invalid tag error"),
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_stream_respond() {
+        let req = TestRequest::default().to_http_request();
+        let res = Element::new("p")
+            .with_node("Hello world")
+            .respond_to(&req)
+            .await
+            .unwrap();
+
+        assert_eq!(res.body().size(), BodySize::Sized(18));
+
+        let res = Element::new("ul")
+            .with_nodes((0..10000).map(|i| Element::new("li").with_node(i.to_string()).into()))
+            .respond_to(&req)
+            .await
+            .unwrap();
+
+        assert_eq!(res.body().size(), BodySize::Stream);
+    }
+}
@@ -0,0 +1,519 @@
+use std::borrow::Cow;
+
+use crate::util::render::{RendererConfig, CONFIG};
+use crate::{Attribute, Document, Element, Node, Nodes, Text};
+
+pub fn render_text<T>(item: &T) -> String
+where
+    T: RenderText,
+{
+    render_text_with(item, &CONFIG)
+}
+
+pub fn render_text_with<T>(item: &T, config: &RendererConfig) -> String
+where
+    T: RenderText,
+{
+    let mut renderer = TextRenderer::new(config);
+
+    item.render_text(&mut renderer);
+    renderer.finish()
+}
+
+pub trait RenderText {
+    fn render_text(&self, renderer: &mut TextRenderer);
+}
+
+pub struct TextRenderer<'a> {
+    config: &'a RendererConfig,
+    output: String,
+    prefix: String,
+    marker: Option<String>,
+    pending: usize,
+    gap: usize,
+    line_start: bool,
+    preformatted: bool,
+    lists: usize,
+}
+
+impl<'a> TextRenderer<'a> {
+    pub fn new(config: &'a RendererConfig) -> Self {
+        Self {
+            config,
+            output: String::new(),
+            prefix: String::new(),
+            marker: None,
+            pending: 0,
+            gap: 0,
+            line_start: true,
+            preformatted: false,
+            lists: 0,
+        }
+    }
+
+    pub fn render<T>(&mut self, item: &T)
+    where
+        T: RenderText,
+    {
+        item.render_text(self)
+    }
+
+    pub fn text(&mut self, text: &str) {
+        if self.preformatted {
+            for (index, line) in text.split('\n').enumerate() {
+                if index > 0 {
+                    self.line_break();
+                }
+
+                self.write(line);
+            }
+
+            return;
+        }
+
+        let mut collapsed = String::with_capacity(text.len());
+
+        if text.starts_with(char::is_whitespace) && !self.line_start && self.pending == 0 {
+            collapsed.push(' ');
+        }
+
+        for (index, word) in text.split_whitespace().enumerate() {
+            if index > 0 {
+                collapsed.push(' ');
+            }
+
+            collapsed.push_str(word);
+        }
+
+        if text.ends_with(char::is_whitespace) && !text.trim_start().is_empty() {
+            collapsed.push(' ');
+        }
+
+        self.write(&collapsed);
+    }
+
+    pub fn line_break(&mut self) {
+        if self.pending == 0 {
+            self.gap = self.prefix.len();
+        }
+
+        self.pending += 1;
+    }
+
+    pub fn block(&mut self, lines: usize) {
+        if !self.output.is_empty() {
+            if self.pending == 0 {
+                self.gap = self.prefix.len();
+            }
+
+            self.pending = self.pending.max(lines);
+        }
+    }
+
+    pub fn finish(mut self) -> String {
+        let len = self.output.trim_end().len();
+
+        self.output.truncate(len);
+        self.output
+    }
+
+    fn write(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+
+        if self.pending > 0 && !self.output.is_empty() {
+            for index in 0..self.pending {
+                if index > 0 {
+                    let gap = self.gap.min(self.prefix.len());
+
+                    self.output.push_str(self.prefix[..gap].trim_end());
+                }
+
+                self.output.push('\n');
+            }
+
+            self.line_start = true;
+        }
+
+        self.pending = 0;
+
+        if self.line_start {
+            match self.marker.take() {
+                Some(marker) => {
+                    let indent = self.prefix.len() - marker.len();
+
+                    self.output.push_str(&self.prefix[..indent]);
+                    self.output.push_str(&marker);
+                }
+                None => self.output.push_str(&self.prefix),
+            }
+
+            self.line_start = false;
+        }
+
+        self.output.push_str(text);
+    }
+
+    fn element(&mut self, element: &Element) {
+        match element.tag() {
+            "head" | "script" | "style" | "template" => (),
+            "br" => self.line_break(),
+            "hr" => {
+                self.block(2);
+                self.write("---");
+                self.block(2);
+            }
+            "img" => {
+                if let Some(alt) = attr(element, "alt") {
+                    self.text(&alt);
+                }
+            }
+            "a" => self.link(element),
+            "ul" => self.list(element, false),
+            "ol" => self.list(element, true),
+            "li" => self.item(element, "- ".to_owned()),
+            "table" => self.table(element),
+            "pre" => {
+                let preformatted = self.preformatted;
+
+                self.block(2);
+                self.preformatted = true;
+                self.render(element.nodes());
+                self.preformatted = preformatted;
+                self.block(2);
+            }
+            "blockquote" => {
+                let len = self.prefix.len();
+
+                self.block(2);
+                self.prefix.push_str("> ");
+                self.render(element.nodes());
+                self.prefix.truncate(len);
+                self.block(2);
+            }
+            tag if self.config.is_inline(tag) => self.render(element.nodes()),
+            tag => {
+                let lines = match tag {
+                    "p" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "dl" | "figure" => 2,
+                    _ => 1,
+                };
+
+                self.block(lines);
+                self.render(element.nodes());
+                self.block(lines);
+            }
+        }
+    }
+
+    fn link(&mut self, element: &Element) {
+        let start = self.output.len();
+
+        self.render(element.nodes());
+
+        let href = match attr(element, "href") {
+            Some(href) if !href.is_empty() && !href.starts_with('#') => href,
+            _ => return,
+        };
+        let label = self.output[start..].trim();
+
+        if label.is_empty() {
+            self.write(&href);
+        } else if label != href {
+            self.write(&format!(" ({})", href));
+        }
+    }
+
+    fn list(&mut self, element: &Element, ordered: bool) {
+        let lines = if self.lists == 0 { 2 } else { 1 };
+        let mut index = attr(element, "start")
+            .and_then(|start| start.parse().ok())
+            .unwrap_or(1);
+
+        self.block(lines);
+        self.lists += 1;
+
+        for node in element.nodes() {
+            match node {
+                Node::Element(item) if item.tag() == "li" => {
+                    let marker = if ordered {
+                        format!("{}. ", index)
+                    } else {
+                        "- ".to_owned()
+                    };
+
+                    index += 1;
+                    self.item(item, marker);
+                }
+                node => self.render(node),
+            }
+        }
+
+        self.lists -= 1;
+        self.block(lines);
+    }
+
+    fn item(&mut self, element: &Element, marker: String) {
+        let len = self.prefix.len();
+
+        self.block(1);
+        self.prefix.push_str(&" ".repeat(marker.len()));
+        self.marker = Some(marker);
+        self.render(element.nodes());
+        self.marker = None;
+        self.prefix.truncate(len);
+        self.block(1);
+    }
+
+    fn table(&mut self, element: &Element) {
+        let mut rows = Vec::new();
+
+        collect_rows(self.config, element, &mut rows);
+
+        let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let widths = (0..columns)
+            .map(|column| {
+                rows.iter()
+                    .filter_map(|row| row.get(column))
+                    .map(|cell| cell.chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect::<Vec<_>>();
+
+        self.block(2);
+
+        for row in rows {
+            let mut line = String::new();
+
+            for (column, cell) in row.iter().enumerate() {
+                if column > 0 {
+                    line.push_str("  ");
+                }
+
+                line.push_str(cell);
+
+                for _ in cell.chars().count()..widths[column] {
+                    line.push(' ');
+                }
+            }
+
+            self.block(1);
+            self.write(line.trim_end());
+        }
+
+        self.block(2);
+    }
+}
+
+impl RenderText for Document {
+    fn render_text(&self, renderer: &mut TextRenderer) {
+        renderer.render(self.nodes())
+    }
+}
+
+impl RenderText for Nodes {
+    fn render_text(&self, renderer: &mut TextRenderer) {
+        for node in self {
+            renderer.render(node);
+        }
+    }
+}
+
+impl RenderText for Node {
+    fn render_text(&self, renderer: &mut TextRenderer) {
+        match self {
+            Self::Text(text) => renderer.render(text),
+            Self::Element(element) => renderer.render(element),
+            Self::Raw(_) | Self::Comment(_) | Self::Instruction(_) => (),
+        }
+    }
+}
+
+impl RenderText for Element {
+    fn render_text(&self, renderer: &mut TextRenderer) {
+        renderer.element(self)
+    }
+}
+
+impl RenderText for Text {
+    fn render_text(&self, renderer: &mut TextRenderer) {
+        renderer.text(self.value())
+    }
+}
+
+fn attr<'a>(element: &'a Element, key: &str) -> Option<Cow<'a, str>> {
+    element.get_attr(key).and_then(Attribute::to_value)
+}
+
+fn collect_rows(config: &RendererConfig, element: &Element, rows: &mut Vec<Vec<String>>) {
+    for node in element.nodes() {
+        let element = match node.as_element() {
+            Some(element) => element,
+            None => continue,
+        };
+
+        match element.tag() {
+            "thead" | "tbody" | "tfoot" => collect_rows(config, element, rows),
+            "tr" => {
+                let cells = element
+                    .nodes()
+                    .iter()
+                    .filter_map(Node::as_element)
+                    .filter(|cell| cell.tag() == "td" || cell.tag() == "th")
+                    .map(|cell| {
+                        render_text_with(cell.nodes(), config)
+                            .split_whitespace()
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                    })
+                    .collect();
+
+                rows.push(cells);
+            }
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render_text;
+    use crate::{Document, Element, Node, Text};
+
+    #[test]
+    fn test_render_text_blocks() {
+        let document = Document::new().with_node(
+            Element::new("html")
+                .with_node(Element::new("head").with_node(Element::new("title").with_node("Title")))
+                .with_node(
+                    Element::new("body")
+                        .with_node(Element::new("h1").with_node("Welcome"))
+                        .with_node(
+                            Element::new("p")
+                                .with_node(Text::preserved("Hello "))
+                                .with_node(Element::new("em").with_node("world"))
+                                .with_node("!")
+                                .with_node(Element::new("br"))
+                                .with_node("Second line"),
+                        )
+                        .with_node(
+                            Element::new("p")
+                                .with_node(Element::new("b").with_node("un"))
+                                .with_node("believable"),
+                        )
+                        .with_node(Element::new("div").with_node("One"))
+                        .with_node(Element::new("div").with_node("Two"))
+                        .with_node(Node::comment("hidden"))
+                        .with_node(Element::new("script").with_node("alert(1)"))
+                        .with_node(Element::new("hr"))
+                        .with_node(
+                            Element::new("pre").with_node(Text::preserved("fn main() {\n    1\n}")),
+                        )
+                        .with_node(
+                            Element::new("blockquote")
+                                .with_node(Element::new("p").with_node("Quoted"))
+                                .with_node(Element::new("p").with_node("Twice")),
+                        ),
+                ),
+        );
+
+        assert_eq!(
+            render_text(&document),
+            "Welcome
+
+Hello world!
+Second line
+
+unbelievable
+
+One
+Two
+
+---
+
+fn main() {
+    1
+}
+
+> Quoted
+>
+> Twice"
+        );
+    }
+
+    #[test]
+    fn test_render_text_lists() {
+        let element = Element::new("div")
+            .with_node(
+                Element::new("ul")
+                    .with_node(Element::new("li").with_node("One"))
+                    .with_node(
+                        Element::new("li").with_node("Two").with_node(
+                            Element::new("ol")
+                                .with_attr("start", "3")
+                                .with_node(Element::new("li").with_node("Three"))
+                                .with_node(Element::new("li").with_node("Four")),
+                        ),
+                    ),
+            )
+            .with_node(
+                Element::new("p")
+                    .with_node(Text::preserved("See "))
+                    .with_node(
+                        Element::new("a")
+                            .with_attr("href", "https://example.com")
+                            .with_node("the docs"),
+                    )
+                    .with_node(Text::preserved(" "))
+                    .with_node(
+                        Element::new("a")
+                            .with_attr("href", "https://example.com")
+                            .with_node("https://example.com"),
+                    )
+                    .with_node(Text::preserved(" "))
+                    .with_node(Element::new("img").with_attr("alt", "logo"))
+                    .with_node("."),
+            );
+
+        assert_eq!(
+            render_text(&element),
+            "- One
+- Two
+  3. Three
+  4. Four
+
+See the docs (https://example.com) https://example.com logo."
+        );
+    }
+
+    #[test]
+    fn test_render_text_table() {
+        let element = Element::new("table")
+            .with_node(
+                Element::new("thead").with_node(
+                    Element::new("tr")
+                        .with_node(Element::new("th").with_node("Name"))
+                        .with_node(Element::new("th").with_node("Quantity")),
+                ),
+            )
+            .with_node(
+                Element::new("tbody")
+                    .with_node(
+                        Element::new("tr")
+                            .with_node(Element::new("td").with_node("Apples"))
+                            .with_node(Element::new("td").with_node("3")),
+                    )
+                    .with_node(
+                        Element::new("tr")
+                            .with_node(Element::new("td").with_node("Kiwi"))
+                            .with_node(Element::new("td").with_node("12")),
+                    ),
+            );
+
+        assert_eq!(
+            render_text(&element),
+            "Name    Quantity\nApples  3\nKiwi    12"
+        );
+    }
+}
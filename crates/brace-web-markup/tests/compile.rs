@@ -0,0 +1,6 @@
+#[test]
+fn test_markup_compile_errors() {
+    let tests = trybuild::TestCases::new();
+
+    tests.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,113 @@
+use brace_web_markup::util::render::render;
+use brace_web_markup::{markup, markup_str, Attribute, Comment, Element, Node, Nodes, Raw, Text};
+
+#[test]
+fn test_markup_static() {
+    let nodes = markup! {
+        /// Navigation
+        nav class = "menu", hidden {
+            a href = url("/about us"), data-index = 1 | "About"
+            a href = "/contact", rel = ["next", "noopener"] {
+                "Contact"
+                "us"
+            }
+        }
+        input type = "checkbox", checked = false
+        !"<hr>"
+    };
+
+    assert_eq!(
+        nodes,
+        Nodes::from(vec![
+            Node::from(Comment::from("Navigation")),
+            Node::from(
+                Element::new("nav")
+                    .with_attr("class", "menu")
+                    .with_attr("hidden", true)
+                    .with_node(
+                        Element::new("a")
+                            .with_attr("href", Attribute::url("/about us"))
+                            .with_attr("data-index", 1)
+                            .with_node(Text::new("About")),
+                    )
+                    .with_node(
+                        Element::new("a")
+                            .with_attr("href", "/contact")
+                            .with_attr("rel", vec!["next", "noopener"])
+                            .with_node(Text::new("Contact"))
+                            .with_node(Text::new("us")),
+                    ),
+            ),
+            Node::from(
+                Element::new("input")
+                    .with_attr("type", "checkbox")
+                    .with_attr("checked", false),
+            ),
+            Node::from(Raw::from("<hr>")),
+        ])
+    );
+
+    let html = markup_str! {
+        /// Navigation
+        nav class = "menu", hidden {
+            a href = url("/about us"), data-index = 1 | "About"
+            a href = "/contact", rel = ["next", "noopener"] {
+                "Contact"
+                "us"
+            }
+        }
+        input type = "checkbox", checked = false
+        !"<hr>"
+    };
+
+    assert_eq!(html, render(&nodes).unwrap());
+    assert_eq!(
+        html,
+        "<!--Navigation--><nav class=\"menu\" hidden><a href=\"/about%20us\" data-index=\"1\">\
+         About</a><a href=\"/contact\" rel=\"next noopener\">Contactus</a></nav>\
+         <input type=\"checkbox\" /><hr>"
+    );
+}
+
+#[test]
+fn test_markup_expressions() {
+    let title = "Hello & welcome";
+    let items = ["one", "two"];
+    let active = Some("active");
+
+    let nodes = markup! {
+        h1 class = (active) | (title)
+        ul {
+            (items
+                .iter()
+                .flat_map(|item| markup! { li | (*item) })
+                .collect::<Vec<_>>())
+        }
+        p class = (None::<&str>) {
+            "("
+            (items.len().to_string())
+            ")"
+        }
+        script | "if (a </b) {}"
+    };
+
+    assert_eq!(
+        render(&nodes).unwrap(),
+        "<h1 class=\"active\">Hello &amp; welcome</h1><ul><li>one</li><li>two</li></ul>\
+         <p>(2)</p><script>if (a <\\/b) {}</script>"
+    );
+}
+
+#[test]
+fn test_markup_hygiene() {
+    let nodes = markup! { li | "one" };
+    let list = markup! {
+        ul {
+            (nodes.clone())
+            li | "two"
+        }
+    };
+
+    assert_eq!(render(&list).unwrap(), "<ul><li>one</li><li>two</li></ul>");
+    assert_eq!(render(&nodes).unwrap(), "<li>one</li>");
+}
@@ -0,0 +1,172 @@
+use html5ever::parse_document;
+use html5ever::tendril::TendrilSink;
+use markup5ever_rcdom::{Handle, NodeData, RcDom};
+
+use brace_web_markup::tree::document::Doctype;
+use brace_web_markup::util::render::{render, render_with, RendererConfig};
+use brace_web_markup::{Document, Element, Node, Nodes, Text};
+
+fn dom(html: &str) -> String {
+    let dom = parse_document(RcDom::default(), Default::default()).one(html);
+    let mut output = String::new();
+
+    walk(&dom.document, &mut output);
+
+    output
+}
+
+fn walk(handle: &Handle, output: &mut String) {
+    let children = handle.children.borrow();
+
+    for (index, child) in children.iter().enumerate() {
+        match &child.data {
+            NodeData::Doctype { name, .. } => output.push_str(&format!("<!{}>", name)),
+            NodeData::Comment { contents } => output.push_str(&format!("<!--{}-->", contents)),
+            NodeData::Text { contents } => {
+                let text = contents.borrow();
+                let prev = index.checked_sub(1).and_then(|index| children.get(index));
+
+                if text.trim().is_empty() && is_block(prev) && is_block(children.get(index + 1)) {
+                    continue;
+                }
+
+                output.push_str(&format!("{:?}", &**text));
+            }
+            NodeData::Element { name, attrs, .. } => {
+                let mut attrs = attrs
+                    .borrow()
+                    .iter()
+                    .map(|attr| format!(" {}={:?}", attr.name.local, &*attr.value))
+                    .collect::<Vec<_>>();
+
+                attrs.sort();
+                output.push_str(&format!("<{}{}>", name.local, attrs.concat()));
+                walk(child, output);
+                output.push_str(&format!("</{}>", name.local));
+            }
+            _ => (),
+        }
+    }
+}
+
+fn is_block(handle: Option<&Handle>) -> bool {
+    match handle.map(|handle| &handle.data) {
+        Some(NodeData::Element { name, .. }) => match &*name.local {
+            "head" | "body" | "title" | "meta" | "table" | "thead" | "tbody" | "tr" | "td"
+            | "div" | "dl" | "dt" | "dd" | "h1" | "ol" | "ul" | "li" | "p" | "section" => true,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn assert_same_dom(node: &Document) {
+    let html = render(node).unwrap();
+    let minified = render_with(node, &RendererConfig::minify()).unwrap();
+
+    assert!(minified.len() < html.len());
+    assert_eq!(dom(&minified), dom(&html), "{}\n{}", html, minified);
+}
+
+#[test]
+fn test_minify_document() {
+    let cell = |tag: &str, text: &str| Node::from(Element::new(tag).with_node(text));
+
+    assert_same_dom(
+        &Document::new().with_node(
+            Element::new("html")
+                .with_attr("lang", "en")
+                .with_node(
+                    Element::new("head")
+                        .with_node(Element::new("meta").with_attr("charset", "utf-8"))
+                        .with_node(Element::new("title").with_node("Minify")),
+                )
+                .with_node(
+                    Element::new("body")
+                        .with_node(Text::preserved("\n  "))
+                        .with_node(Element::new("h1").with_node("Title"))
+                        .with_node(Text::preserved("\n  "))
+                        .with_node(
+                            Element::new("p")
+                                .with_attr("class", "intro text")
+                                .with_attr("title", "")
+                                .with_attr("data-id", "a=b")
+                                .with_node("Hello")
+                                .with_node(Text::preserved(" "))
+                                .with_node(Element::new("em").with_node("big"))
+                                .with_node(Text::preserved(" "))
+                                .with_node(Element::new("strong").with_node("world"))
+                                .with_node(Element::new("span").with_node(Text::preserved(" "))),
+                        )
+                        .with_node(
+                            Element::new("ul")
+                                .with_node(cell("li", "One"))
+                                .with_node(Text::preserved("\n"))
+                                .with_node(cell("li", "Two")),
+                        )
+                        .with_node(
+                            Element::new("dl")
+                                .with_node(cell("dt", "Term"))
+                                .with_node(cell("dd", "Definition"))
+                                .with_node(cell("dt", "Other")),
+                        )
+                        .with_node(Element::new("p").with_node("Before a table"))
+                        .with_node(
+                            Element::new("table")
+                                .with_node(
+                                    Element::new("thead").with_node(
+                                        Element::new("tr")
+                                            .with_node(cell("th", "Name"))
+                                            .with_node(cell("th", "Size")),
+                                    ),
+                                )
+                                .with_node(
+                                    Element::new("tbody").with_node(
+                                        Element::new("tr")
+                                            .with_node(cell("td", "Apples"))
+                                            .with_node(cell("td", "3")),
+                                    ),
+                                ),
+                        )
+                        .with_node(
+                            Element::new("select")
+                                .with_attr("disabled", true)
+                                .with_node(
+                                    Element::new("optgroup")
+                                        .with_attr("label", "Fruit")
+                                        .with_node(cell("option", "Apple"))
+                                        .with_node(cell("option", "Pear")),
+                                )
+                                .with_node(cell("option", "Other")),
+                        )
+                        .with_node(
+                            Element::new("a")
+                                .with_attr("href", "/")
+                                .with_node(Element::new("p").with_node("Inside a link")),
+                        )
+                        .with_node(
+                            Element::new("section")
+                                .with_node(Element::new("p").with_node("Last"))
+                                .with_node(Element::new("input").with_attr("type", "text")),
+                        )
+                        .with_node(Node::comment("end")),
+                ),
+        ),
+    );
+}
+
+#[test]
+fn test_minify_fragment() {
+    assert_same_dom(
+        &Document::from(Nodes::from(vec![
+            Node::from(Element::new("p").with_node("Quirks mode")),
+            Node::from(
+                Element::new("table")
+                    .with_node(Element::new("tr").with_node(Element::new("td").with_node("Cell"))),
+            ),
+            Node::from(Element::new("p").with_node("Custom")),
+            Node::from(Element::new("my-widget").with_node(Element::new("p").with_node("Widget"))),
+        ]))
+        .with_doctype(Doctype::None),
+    );
+}
@@ -1,5 +1,7 @@
-use brace_web_markup::{body, document, em, html, text, Document};
-use serde_json::{from_str, to_string_pretty};
+use brace_web_markup::{
+    a, body, comment, document, em, html, instruction, raw, text, Attribute, Document,
+};
+use serde_json::{from_str, to_string, to_string_pretty};
 
 #[test]
 fn test_serde_integration() {
@@ -21,3 +23,75 @@ fn test_serde_integration() {
 
     assert_eq!(doc_1, doc_2);
 }
+
+#[test]
+fn test_serde_raw() {
+    let doc = document().with_node(
+        body()
+            .with_node(text("Hello"))
+            .with_node(raw("<em>world</em>")),
+    );
+
+    let str_1 = to_string(&doc).unwrap();
+
+    assert_eq!(
+        str_1,
+        r#"{"nodes":[{"tag":"body","attrs":{},"nodes":["Hello",{"raw":"<em>world</em>"}]}]}"#
+    );
+
+    let doc_1: Document = from_str(&str_1).unwrap();
+
+    assert_eq!(doc_1, doc);
+}
+
+#[test]
+fn test_serde_comment() {
+    let doc = document().with_node(body().with_node(comment("Hello")).with_node(text("world")));
+
+    let str_1 = to_string(&doc).unwrap();
+
+    assert_eq!(
+        str_1,
+        r#"{"nodes":[{"tag":"body","attrs":{},"nodes":[{"comment":"Hello"},"world"]}]}"#
+    );
+
+    let doc_1: Document = from_str(&str_1).unwrap();
+
+    assert_eq!(doc_1, doc);
+}
+
+#[test]
+fn test_serde_instruction() {
+    let doc = document().with_node(instruction("xml-stylesheet", "href=\"style.xsl\""));
+
+    let str_1 = to_string(&doc).unwrap();
+
+    assert_eq!(
+        str_1,
+        r#"{"nodes":[{"target":"xml-stylesheet","data":"href=\"style.xsl\""}]}"#
+    );
+
+    let doc_1: Document = from_str(&str_1).unwrap();
+
+    assert_eq!(doc_1, doc);
+}
+
+#[test]
+fn test_serde_attribute() {
+    let doc = document().with_node(
+        a().with_attr("href", Attribute::url("/"))
+            .with_attr("tabindex", 1)
+            .with_attr("class", vec!["one", "two"]),
+    );
+
+    let str_1 = to_string(&doc).unwrap();
+
+    assert_eq!(
+        str_1,
+        r#"{"nodes":[{"tag":"a","attrs":{"href":{"url":"/"},"tabindex":1.0,"class":["one","two"]},"nodes":[]}]}"#
+    );
+
+    let doc_1: Document = from_str(&str_1).unwrap();
+
+    assert_eq!(doc_1, doc);
+}
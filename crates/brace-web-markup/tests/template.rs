@@ -108,3 +108,47 @@ fn test_template_3() {
         ))
     );
 }
+
+#[test]
+fn test_template_4() {
+    assert_eq!(
+        parse(include_str!("../fixtures/template-4.txt"), document),
+        Ok((
+            Node::element(
+                Element::new("div")
+                    .with_attr("class", "content")
+                    .with_nodes(vec![
+                        Node::comment("This comment is rendered."),
+                        Element::new("p").with_node(Text::new("Before")).into(),
+                        Node::raw("<hr class=\"divider\">"),
+                        Element::new("p").with_node(Text::new("After")).into(),
+                    ]),
+            )
+            .into(),
+            ""
+        )),
+    );
+}
+
+#[test]
+fn test_template_5() {
+    assert_eq!(
+        parse(include_str!("../fixtures/template-5.txt"), document),
+        Ok((
+            Node::element(Element::new("div").with_nodes(vec![
+                Element::new("pre")
+                    .with_node(Text::preserved(
+                        "fn main() {\n    println!(\"Hello, world!\");\n}"
+                    ))
+                    .into(),
+                Element::new("p")
+                    .with_node(Text::new("Hello"))
+                    .with_node(Element::new("em").with_node(Text::new("world")))
+                    .with_node(Text::new("!"))
+                    .into(),
+            ]))
+            .into(),
+            ""
+        )),
+    );
+}
@@ -0,0 +1,7 @@
+use brace_web_markup::markup;
+
+fn main() {
+    markup! {
+        a href = home | "Home"
+    };
+}
@@ -0,0 +1,9 @@
+use brace_web_markup::markup_str;
+
+fn main() {
+    let name = "world";
+
+    markup_str! {
+        p | (name)
+    };
+}
@@ -0,0 +1,13 @@
+use brace_web_markup::markup;
+
+fn main() {
+    let path = "/about";
+
+    markup! {
+        a href = link("/about") | "About"
+    };
+
+    markup! {
+        a href = url(path) | "About"
+    };
+}